@@ -1,43 +1,408 @@
-use crate::db::DbState;
-use crate::error::ApiError;
-use crate::services::backup_service;
+use super::{spawn_operation, with_db};
+use crate::db::get_db_path;
+use crate::error::{ApiError, AppError};
+use crate::models::{
+    BackupFileDto, BackupSettings, ConflictPolicy, CsvColumnMapping, CsvPreview, ExportScope, ImportMode,
+    ImportPreview, ImportTarget, OperationKind,
+};
+use crate::services::sync_service::{self, SyncScope};
+use crate::services::{
+    auto_backup_service, backup_service, chatgpt_service, claude_service, csv_service, markdown_service,
+    preview_service, schema_service, site_service, snapshot_service,
+};
+use std::path::PathBuf;
 use log::{error, info};
-use tauri::State;
-
-/// 导出数据
-#[tauri::command]
-pub fn export_data(targetPath: String, db: State<DbState>) -> Result<(), ApiError> {
-    info!("export_data 调用: targetPath={}", targetPath);
-    
-    let conn = db.0.lock().map_err(|e| {
-        error!("获取数据库锁失败: {}", e);
-        ApiError {
-            code: "LOCK_ERROR".to_string(),
-            message: format!("获取数据库锁失败: {}", e),
+use tauri::AppHandle;
+
+/// 导出数据（后台执行，`.zip` 路径写入压缩包，提供口令时加密），返回任务 ID，完成后发送 `operation-finished` 事件
+///
+/// 指定 `scope` 时只导出选中的项目、任务和提示词。
+#[tauri::command]
+pub async fn export_data(
+    targetPath: String,
+    passphrase: Option<String>,
+    scope: Option<ExportScope>,
+    app: AppHandle,
+) -> Result<String, ApiError> {
+    info!(
+        "export_data 调用: targetPath={}, encrypted={}, scope={:?}",
+        targetPath, passphrase.is_some(), scope
+    );
+
+    Ok(spawn_operation(app, OperationKind::Export, move |db| {
+        let conn = db.read()?;
+
+        backup_service::export_to_file(&conn, &targetPath, passphrase.as_deref(), scope.as_ref())
+            .map_err(|e| {
+                error!("export_data 错误: {:?}", e);
+                e.into()
+            })
+    }))
+}
+
+/// 导入数据（后台执行），返回任务 ID，完成后发送 `operation-finished` 事件，结果为 `ImportReport`
+///
+/// 先读取、解密并校验备份文件，通过后才获取写连接；加密文件未提供口令时以 `PASSPHRASE_REQUIRED` 失败。
+/// 写入前自动创建数据库快照，导入结果不理想时可从快照恢复。
+/// 指定 `target` 时将内容并入该项目或任务，此时不能使用覆盖模式。
+#[tauri::command]
+pub async fn import_data(
+    sourcePath: String,
+    mode: Option<ImportMode>,
+    conflictPolicy: Option<ConflictPolicy>,
+    passphrase: Option<String>,
+    target: Option<ImportTarget>,
+    app: AppHandle,
+) -> Result<String, ApiError> {
+    info!(
+        "import_data 调用: sourcePath={}, mode={:?}, conflictPolicy={:?}, encrypted={}, target={:?}",
+        sourcePath, mode, conflictPolicy, passphrase.is_some(), target
+    );
+
+    if target.is_some() && mode == Some(ImportMode::Replace) {
+        return Err(AppError::ValidationError("导入到指定项目或任务时不能使用覆盖模式".to_string()).into());
+    }
+
+    let handle = app.clone();
+    Ok(spawn_operation(app, OperationKind::Import, move |db| {
+        let data = backup_service::read_backup_file(&PathBuf::from(&sourcePath), passphrase.as_deref())
+            .map_err(|e| {
+                error!("import_data 读取备份错误: {:?}", e);
+                ApiError::from(e)
+            })?;
+        let mut conn = db.write()?;
+
+        let snapshot = get_db_path(&handle)
+            .and_then(|path| snapshot_service::snapshot_dir(&path))
+            .and_then(|dir| snapshot_service::create_snapshot(&conn, &dir, "导入前", true))
+            .map_err(|e| {
+                error!("import_data 创建快照错误: {:?}", e);
+                ApiError::from(e)
+            })?;
+        info!("导入前已创建快照: {}", snapshot.file_name);
+
+        let policy = conflictPolicy.unwrap_or_default();
+
+        match target {
+            Some(target) => backup_service::import_into_target(&mut conn, data, target, policy),
+            None => backup_service::import_export_data(&mut conn, data, mode.unwrap_or_default(), policy),
         }
-    })?;
+        .map_err(|e| {
+            error!("import_data 错误: {:?}", e);
+            e.into()
+        })
+        .inspect(|_| sync_service::spawn_sync(handle, SyncScope::All))
+    }))
+}
+
+/// 预览导入：解析备份文件，返回内容统计和与当前数据的差异，不写入数据库
+#[tauri::command]
+pub async fn preview_import(
+    sourcePath: String,
+    passphrase: Option<String>,
+    app: AppHandle,
+) -> Result<ImportPreview, ApiError> {
+    info!("preview_import 调用: sourcePath={}", sourcePath);
+
+    with_db(app, move |db| {
+        let data = backup_service::read_backup_file(&PathBuf::from(&sourcePath), passphrase.as_deref())
+            .map_err(|e| {
+                error!("preview_import 读取备份错误: {:?}", e);
+                ApiError::from(e)
+            })?;
+        let conn = db.read()?;
 
-    backup_service::export_to_file(&conn, &targetPath).map_err(|e| {
-        error!("export_data 错误: {:?}", e);
-        e.into()
+        preview_service::preview_import(&conn, &data).map_err(|e| {
+            error!("preview_import 错误: {:?}", e);
+            e.into()
+        })
     })
+    .await
 }
 
-/// 导入数据
+/// 获取当前导出格式的 JSON Schema，供外部工具校验或生成导出文件
 #[tauri::command]
-pub fn import_data(sourcePath: String, db: State<DbState>) -> Result<(), ApiError> {
-    info!("import_data 调用: sourcePath={}", sourcePath);
-    
-    let mut conn = db.0.lock().map_err(|e| {
-        error!("获取数据库锁失败: {}", e);
-        ApiError {
-            code: "LOCK_ERROR".to_string(),
-            message: format!("获取数据库锁失败: {}", e),
-        }
-    })?;
+pub fn get_export_schema() -> String {
+    schema_service::EXPORT_SCHEMA.to_string()
+}
+
+/// 导出为 Markdown 目录（后台执行），返回任务 ID，完成后发送 `operation-finished` 事件，结果为写入的文件数
+#[tauri::command]
+pub async fn export_markdown(targetDir: String, app: AppHandle) -> Result<String, ApiError> {
+    info!("export_markdown 调用: targetDir={}", targetDir);
+
+    Ok(spawn_operation(app, OperationKind::MarkdownExport, move |db| {
+        let conn = db.read()?;
+
+        markdown_service::export_vault(&conn, &PathBuf::from(&targetDir)).map_err(|e| {
+            error!("export_markdown 错误: {:?}", e);
+            e.into()
+        })
+    }))
+}
+
+/// 从 Markdown 目录导入（后台执行），默认合并到现有数据；完成后发送 `operation-finished` 事件，结果为 `ImportReport`
+#[tauri::command]
+pub async fn import_markdown(
+    sourceDir: String,
+    mode: Option<ImportMode>,
+    conflictPolicy: Option<ConflictPolicy>,
+    app: AppHandle,
+) -> Result<String, ApiError> {
+    info!(
+        "import_markdown 调用: sourceDir={}, mode={:?}, conflictPolicy={:?}",
+        sourceDir, mode, conflictPolicy
+    );
+
+    let handle = app.clone();
+    Ok(spawn_operation(app, OperationKind::MarkdownImport, move |db| {
+        let mut conn = db.write()?;
+
+        markdown_service::import_folder(
+            &mut conn,
+            &PathBuf::from(&sourceDir),
+            mode.unwrap_or(ImportMode::Merge),
+            conflictPolicy.unwrap_or_default(),
+        )
+        .map_err(|e| {
+            error!("import_markdown 错误: {:?}", e);
+            e.into()
+        })
+        .inspect(|_| sync_service::spawn_sync(handle, SyncScope::All))
+    }))
+}
+
+/// 导出为静态网站（后台执行），返回任务 ID，完成后发送 `operation-finished` 事件，结果为写入的文件数
+///
+/// 指定 `scope` 时只导出选中的项目、任务和提示词。
+#[tauri::command]
+pub async fn export_site(targetDir: String, scope: Option<ExportScope>, app: AppHandle) -> Result<String, ApiError> {
+    info!("export_site 调用: targetDir={}, scope={:?}", targetDir, scope);
+
+    Ok(spawn_operation(app, OperationKind::SiteExport, move |db| {
+        let conn = db.read()?;
+
+        site_service::export_site(&conn, &PathBuf::from(&targetDir), scope.as_ref()).map_err(|e| {
+            error!("export_site 错误: {:?}", e);
+            e.into()
+        })
+    }))
+}
+
+/// 导出为 CSV（后台执行），返回任务 ID，完成后发送 `operation-finished` 事件，结果为写入的行数
+#[tauri::command]
+pub async fn export_csv(targetPath: String, app: AppHandle) -> Result<String, ApiError> {
+    info!("export_csv 调用: targetPath={}", targetPath);
+
+    Ok(spawn_operation(app, OperationKind::CsvExport, move |db| {
+        let conn = db.read()?;
+
+        csv_service::export_csv(&conn, &PathBuf::from(&targetPath)).map_err(|e| {
+            error!("export_csv 错误: {:?}", e);
+            e.into()
+        })
+    }))
+}
+
+/// 预览 CSV：返回表头、前几行数据和推测的列映射
+#[tauri::command]
+pub async fn preview_csv(sourcePath: String, app: AppHandle) -> Result<CsvPreview, ApiError> {
+    info!("preview_csv 调用: sourcePath={}", sourcePath);
+
+    with_db(app, move |_| {
+        csv_service::preview_csv(&PathBuf::from(&sourcePath)).map_err(|e| {
+            error!("preview_csv 错误: {:?}", e);
+            e.into()
+        })
+    })
+    .await
+}
+
+/// 按列映射导入 CSV（后台执行），默认合并到现有数据；完成后发送 `operation-finished` 事件，结果为 `ImportReport`
+#[tauri::command]
+pub async fn import_csv(
+    sourcePath: String,
+    mapping: CsvColumnMapping,
+    mode: Option<ImportMode>,
+    conflictPolicy: Option<ConflictPolicy>,
+    app: AppHandle,
+) -> Result<String, ApiError> {
+    info!(
+        "import_csv 调用: sourcePath={}, mode={:?}, conflictPolicy={:?}",
+        sourcePath, mode, conflictPolicy
+    );
+
+    let handle = app.clone();
+    Ok(spawn_operation(app, OperationKind::CsvImport, move |db| {
+        let mut conn = db.write()?;
 
-    backup_service::import_from_file(&mut conn, &sourcePath).map_err(|e| {
-        error!("import_data 错误: {:?}", e);
-        e.into()
+        csv_service::import_csv(
+            &mut conn,
+            &PathBuf::from(&sourcePath),
+            &mapping,
+            mode.unwrap_or(ImportMode::Merge),
+            conflictPolicy.unwrap_or_default(),
+        )
+        .map_err(|e| {
+            error!("import_csv 错误: {:?}", e);
+            e.into()
+        })
+        .inspect(|_| sync_service::spawn_sync(handle, SyncScope::All))
+    }))
+}
+
+/// 导入 ChatGPT 数据导出（后台执行），每个对话作为指定项目下的一个任务；完成后发送 `operation-finished` 事件，结果为 `ImportReport`
+///
+/// `sourcePath` 可以是 conversations.json 或官方导出的压缩包；短于 `minLength` 个字符的消息不导入。
+#[tauri::command]
+pub async fn import_chatgpt(
+    sourcePath: String,
+    projectId: i64,
+    minLength: Option<usize>,
+    conflictPolicy: Option<ConflictPolicy>,
+    app: AppHandle,
+) -> Result<String, ApiError> {
+    info!(
+        "import_chatgpt 调用: sourcePath={}, projectId={}, minLength={:?}, conflictPolicy={:?}",
+        sourcePath, projectId, minLength, conflictPolicy
+    );
+
+    let handle = app.clone();
+    Ok(spawn_operation(app, OperationKind::ChatgptImport, move |db| {
+        let mut conn = db.write()?;
+
+        chatgpt_service::import_conversations(
+            &mut conn,
+            &PathBuf::from(&sourcePath),
+            projectId,
+            minLength.unwrap_or(0),
+            conflictPolicy.unwrap_or_default(),
+        )
+        .map_err(|e| {
+            error!("import_chatgpt 错误: {:?}", e);
+            e.into()
+        })
+        .inspect(|_| sync_service::spawn_sync(handle, SyncScope::All))
+    }))
+}
+
+/// 导入 Claude.ai 数据导出（后台执行），每个对话作为指定项目下的一个任务；完成后发送 `operation-finished` 事件，结果为 `ImportReport`
+///
+/// 沿用导出中的 uuid，重复导入较新的导出时只新增之前没有的消息；短于 `minLength` 个字符的消息不导入。
+#[tauri::command]
+pub async fn import_claude(
+    sourcePath: String,
+    projectId: i64,
+    minLength: Option<usize>,
+    conflictPolicy: Option<ConflictPolicy>,
+    app: AppHandle,
+) -> Result<String, ApiError> {
+    info!(
+        "import_claude 调用: sourcePath={}, projectId={}, minLength={:?}, conflictPolicy={:?}",
+        sourcePath, projectId, minLength, conflictPolicy
+    );
+
+    let handle = app.clone();
+    Ok(spawn_operation(app, OperationKind::ClaudeImport, move |db| {
+        let mut conn = db.write()?;
+
+        claude_service::import_conversations(
+            &mut conn,
+            &PathBuf::from(&sourcePath),
+            projectId,
+            minLength.unwrap_or(0),
+            conflictPolicy.unwrap_or_default(),
+        )
+        .map_err(|e| {
+            error!("import_claude 错误: {:?}", e);
+            e.into()
+        })
+        .inspect(|_| sync_service::spawn_sync(handle, SyncScope::All))
+    }))
+}
+
+/// 获取自动备份设置
+#[tauri::command]
+pub async fn get_backup_settings(app: AppHandle) -> Result<BackupSettings, ApiError> {
+    with_db(app, move |db| {
+        let conn = db.read()?;
+
+        auto_backup_service::get_settings(&conn).map_err(Into::into)
     })
+    .await
+}
+
+/// 保存自动备份设置
+#[tauri::command]
+pub async fn set_backup_settings(settings: BackupSettings, app: AppHandle) -> Result<(), ApiError> {
+    info!("set_backup_settings 调用: {:?}", settings);
+
+    with_db(app, move |db| {
+        let conn = db.write()?;
+
+        auto_backup_service::set_settings(&conn, &settings).map_err(|e| {
+            error!("set_backup_settings 错误: {:?}", e);
+            e.into()
+        })
+    })
+    .await
+}
+
+/// 列出备份目录中的备份，最新的在前
+#[tauri::command]
+pub async fn list_backups(app: AppHandle) -> Result<Vec<BackupFileDto>, ApiError> {
+    let handle = app.clone();
+    with_db(app, move |db| {
+        let conn = db.read()?;
+        let settings = auto_backup_service::get_settings(&conn)?;
+        drop(conn);
+
+        auto_backup_service::backup_dir(&handle, &settings)
+            .and_then(|dir| auto_backup_service::list_backups(&dir))
+            .map_err(|e| {
+                error!("list_backups 错误: {:?}", e);
+                e.into()
+            })
+    })
+    .await
+}
+
+/// 立即写入一份备份（并按策略轮换）
+#[tauri::command]
+pub async fn create_backup(app: AppHandle) -> Result<BackupFileDto, ApiError> {
+    info!("create_backup 调用");
+
+    let handle = app.clone();
+    with_db(app, move |db| {
+        let conn = db.read()?;
+        let settings = auto_backup_service::get_settings(&conn)?;
+
+        auto_backup_service::backup_dir(&handle, &settings)
+            .and_then(|dir| auto_backup_service::create_backup(&conn, &dir))
+            .map_err(|e| {
+                error!("create_backup 错误: {:?}", e);
+                e.into()
+            })
+    })
+    .await
+}
+
+/// 从备份恢复（后台执行，覆盖现有数据），返回任务 ID，完成后发送 `operation-finished` 事件，结果为 `ImportReport`
+#[tauri::command]
+pub async fn restore_backup(fileName: String, app: AppHandle) -> Result<String, ApiError> {
+    info!("restore_backup 调用: fileName={}", fileName);
+
+    let handle = app.clone();
+    Ok(spawn_operation(app, OperationKind::BackupRestore, move |db| {
+        let mut conn = db.write()?;
+        let settings = auto_backup_service::get_settings(&conn)?;
+
+        auto_backup_service::backup_dir(&handle, &settings)
+            .and_then(|dir| auto_backup_service::restore_backup(&mut conn, &dir, &fileName))
+            .map_err(|e| {
+                error!("restore_backup 错误: {:?}", e);
+                e.into()
+            })
+            .inspect(|_| sync_service::spawn_sync(handle, SyncScope::All))
+    }))
 }
@@ -1,14 +1,27 @@
 // Tauri Commands 层
+mod runtime;
 mod project;
 mod task;
 mod prompt;
+mod revision;
+mod tag;
 mod search;
 mod settings;
 mod backup;
+mod snapshot;
+mod sync;
+mod trash;
 
 pub use project::*;
 pub use task::*;
 pub use prompt::*;
+pub use revision::*;
+pub use tag::*;
 pub use search::*;
 pub use settings::*;
 pub use backup::*;
+pub use snapshot::*;
+pub use sync::*;
+pub use trash::*;
+
+use runtime::{spawn_operation, with_db};
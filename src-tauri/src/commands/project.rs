@@ -1,49 +1,63 @@
-use crate::db::DbState;
+use super::with_db;
 use crate::error::ApiError;
-use crate::models::ProjectDto;
+use crate::models::{ProjectDto, SortBy};
 use crate::repositories::project_repository;
-use tauri::State;
+use crate::services::sync_service::{self, SyncScope};
+use tauri::AppHandle;
 
 /// 获取所有项目
 #[tauri::command]
-pub fn list_projects(db: State<DbState>) -> Result<Vec<ProjectDto>, ApiError> {
-    let conn = db.0.lock().map_err(|e| ApiError {
-        code: "LOCK_ERROR".to_string(),
-        message: format!("获取数据库锁失败: {}", e),
-    })?;
+pub async fn list_projects(sortBy: Option<SortBy>, app: AppHandle) -> Result<Vec<ProjectDto>, ApiError> {
+    with_db(app, move |db| {
+        let conn = db.read()?;
 
-    project_repository::list_projects(&conn).map_err(Into::into)
+        project_repository::list_projects(&conn, sortBy.unwrap_or_default()).map_err(Into::into)
+    })
+    .await
 }
 
 /// 创建项目
 #[tauri::command]
-pub fn create_project(name: String, db: State<DbState>) -> Result<ProjectDto, ApiError> {
-    let conn = db.0.lock().map_err(|e| ApiError {
-        code: "LOCK_ERROR".to_string(),
-        message: format!("获取数据库锁失败: {}", e),
-    })?;
+pub async fn create_project(name: String, app: AppHandle) -> Result<ProjectDto, ApiError> {
+    with_db(app, move |db| {
+        let conn = db.write()?;
 
-    project_repository::create_project(&conn, &name).map_err(Into::into)
+        project_repository::create_project(&conn, &name).map_err(Into::into)
+    })
+    .await
 }
 
 /// 更新项目
 #[tauri::command]
-pub fn update_project(id: i64, name: String, db: State<DbState>) -> Result<(), ApiError> {
-    let conn = db.0.lock().map_err(|e| ApiError {
-        code: "LOCK_ERROR".to_string(),
-        message: format!("获取数据库锁失败: {}", e),
-    })?;
-
-    project_repository::update_project(&conn, id, &name).map_err(Into::into)
+pub async fn update_project(id: i64, name: String, app: AppHandle) -> Result<(), ApiError> {
+    with_db(app.clone(), move |db| {
+        let conn = db.write()?;
+
+        project_repository::update_project(&conn, id, &name).map_err(Into::into)
+    })
+    .await
+    .inspect(|_| sync_service::spawn_sync(app, SyncScope::All))
 }
 
 /// 删除项目
 #[tauri::command]
-pub fn delete_project(id: i64, db: State<DbState>) -> Result<(), ApiError> {
-    let conn = db.0.lock().map_err(|e| ApiError {
-        code: "LOCK_ERROR".to_string(),
-        message: format!("获取数据库锁失败: {}", e),
-    })?;
+pub async fn delete_project(id: i64, app: AppHandle) -> Result<(), ApiError> {
+    with_db(app.clone(), move |db| {
+        let conn = db.write()?;
+
+        project_repository::delete_project(&conn, id).map_err(Into::into)
+    })
+    .await
+    .inspect(|_| sync_service::spawn_sync(app, SyncScope::All))
+}
+
+/// 按全局唯一 ID 获取项目
+#[tauri::command]
+pub async fn get_project_by_uuid(uuid: String, app: AppHandle) -> Result<ProjectDto, ApiError> {
+    with_db(app, move |db| {
+        let conn = db.read()?;
 
-    project_repository::delete_project(&conn, id).map_err(Into::into)
+        project_repository::get_project_by_uuid(&conn, &uuid).map_err(Into::into)
+    })
+    .await
 }
@@ -0,0 +1,83 @@
+use super::with_db;
+use crate::error::ApiError;
+use crate::models::{PromptEntryDto, PromptRevisionDto};
+use crate::repositories::{prompt_repository, revision_repository};
+use crate::services::sync_service::{self, SyncScope};
+use log::{error, info};
+use tauri::AppHandle;
+
+/// 获取提示词的修订历史
+#[tauri::command]
+pub async fn list_prompt_revisions(
+    promptId: i64,
+    app: AppHandle,
+) -> Result<Vec<PromptRevisionDto>, ApiError> {
+    info!("list_prompt_revisions 调用: promptId={}", promptId);
+
+    with_db(app, move |db| {
+        let conn = db.read()?;
+
+        revision_repository::list_revisions(&conn, promptId).map_err(|e| {
+            error!("list_prompt_revisions 错误: {:?}", e);
+            e.into()
+        })
+    })
+    .await
+}
+
+/// 获取单条修订记录
+#[tauri::command]
+pub async fn get_prompt_revision(id: i64, app: AppHandle) -> Result<PromptRevisionDto, ApiError> {
+    info!("get_prompt_revision 调用: id={}", id);
+
+    with_db(app, move |db| {
+        let conn = db.read()?;
+
+        revision_repository::get_revision(&conn, id).map_err(|e| {
+            error!("get_prompt_revision 错误: {:?}", e);
+            e.into()
+        })
+    })
+    .await
+}
+
+/// 将提示词恢复为指定修订
+#[tauri::command]
+pub async fn restore_prompt_revision(id: i64, app: AppHandle) -> Result<PromptEntryDto, ApiError> {
+    info!("restore_prompt_revision 调用: id={}", id);
+
+    with_db(app.clone(), move |db| {
+        let conn = db.write()?;
+
+        prompt_repository::restore_revision(&conn, id).map_err(|e| {
+            error!("restore_prompt_revision 错误: {:?}", e);
+            e.into()
+        })
+    })
+    .await
+    .inspect(|entry| sync_service::spawn_sync(app, SyncScope::Prompt(entry.id)))
+}
+
+/// 清理旧修订，返回删除的条数
+#[tauri::command]
+pub async fn prune_prompt_revisions(
+    promptId: Option<i64>,
+    keepLatest: Option<i64>,
+    before: Option<String>,
+    app: AppHandle,
+) -> Result<usize, ApiError> {
+    info!(
+        "prune_prompt_revisions 调用: promptId={:?}, keepLatest={:?}, before={:?}",
+        promptId, keepLatest, before
+    );
+
+    with_db(app, move |db| {
+        let conn = db.write()?;
+
+        revision_repository::prune_revisions(&conn, promptId, keepLatest, before.as_deref()).map_err(|e| {
+            error!("prune_prompt_revisions 错误: {:?}", e);
+            e.into()
+        })
+    })
+    .await
+}
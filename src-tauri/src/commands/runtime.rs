@@ -0,0 +1,77 @@
+use crate::db::DbState;
+use crate::error::ApiError;
+use crate::models::{OperationFinishedEvent, OperationKind, OPERATION_FINISHED_EVENT};
+use log::{error, info};
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// 后台任务序号
+static NEXT_OPERATION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 在阻塞线程池中执行数据库操作，避免阻塞主线程
+pub async fn with_db<T, F>(app: AppHandle, f: F) -> Result<T, ApiError>
+where
+    F: FnOnce(&DbState) -> Result<T, ApiError> + Send + 'static,
+    T: Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(move || {
+        let db = app.state::<DbState>();
+        f(db.inner())
+    })
+    .await
+    .map_err(|e| {
+        error!("后台任务执行失败: {}", e);
+        ApiError {
+            code: "TASK_ERROR".to_string(),
+            message: format!("后台任务执行失败: {}", e),
+        }
+    })?
+}
+
+/// 在后台启动长任务并立即返回任务 ID，完成后通过 `operation-finished` 事件通知前端
+pub fn spawn_operation<T, F>(app: AppHandle, kind: OperationKind, f: F) -> String
+where
+    F: FnOnce(&DbState) -> Result<T, ApiError> + Send + 'static,
+    T: Serialize + Send + 'static,
+{
+    let operation_id = format!(
+        "{}-{}",
+        chrono::Utc::now().timestamp_millis(),
+        NEXT_OPERATION_ID.fetch_add(1, Ordering::Relaxed)
+    );
+
+    let id = operation_id.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = with_db(app.clone(), f).await;
+
+        let event = match result {
+            Ok(value) => {
+                info!("后台任务完成: {:?} {}", kind, id);
+                OperationFinishedEvent {
+                    operation_id: id,
+                    kind,
+                    success: true,
+                    result: serde_json::to_value(value).ok(),
+                    error: None,
+                }
+            }
+            Err(e) => {
+                error!("后台任务失败: {:?} {} {:?}", kind, id, e);
+                OperationFinishedEvent {
+                    operation_id: id,
+                    kind,
+                    success: false,
+                    result: None,
+                    error: Some(e),
+                }
+            }
+        };
+
+        if let Err(e) = app.emit(OPERATION_FINISHED_EVENT, event) {
+            error!("发送任务完成事件失败: {}", e);
+        }
+    });
+
+    operation_id
+}
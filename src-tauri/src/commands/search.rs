@@ -1,34 +1,53 @@
-use crate::db::DbState;
+use super::with_db;
 use crate::error::ApiError;
-use crate::models::SearchResultDto;
+use crate::models::{QuickSwitchItemDto, SearchResultDto};
 use crate::services::search_service;
 use log::{error, info};
-use tauri::State;
+use tauri::AppHandle;
 
 /// 搜索提示词记录
 #[tauri::command]
-pub fn search_prompt_entries(
+pub async fn search_prompt_entries(
     keyword: String,
     projectId: Option<i64>,
     taskId: Option<i64>,
     limit: Option<i64>,
-    db: State<DbState>,
+    app: AppHandle,
 ) -> Result<Vec<SearchResultDto>, ApiError> {
     info!("search_prompt_entries 调用: keyword={}, projectId={:?}, taskId={:?}", keyword, projectId, taskId);
-    
-    let conn = db.0.lock().map_err(|e| {
-        error!("获取数据库锁失败: {}", e);
-        ApiError {
-            code: "LOCK_ERROR".to_string(),
-            message: format!("获取数据库锁失败: {}", e),
-        }
-    })?;
-
-    let limit = limit.unwrap_or(50);
-
-    search_service::search_prompts(&conn, &keyword, projectId, taskId, limit)
-        .map_err(|e| {
-            error!("search_prompt_entries 错误: {:?}", e);
+
+    with_db(app, move |db| {
+        let conn = db.read()?;
+
+        let limit = limit.unwrap_or(50);
+
+        search_service::search_prompts(&conn, &keyword, projectId, taskId, limit)
+            .map_err(|e| {
+                error!("search_prompt_entries 错误: {:?}", e);
+                e.into()
+            })
+    })
+    .await
+}
+
+/// 快速切换：按名称或拼音查找项目、任务和提示词
+#[tauri::command]
+pub async fn quick_switch(
+    keyword: String,
+    limit: Option<i64>,
+    app: AppHandle,
+) -> Result<Vec<QuickSwitchItemDto>, ApiError> {
+    info!("quick_switch 调用: keyword={}", keyword);
+
+    with_db(app, move |db| {
+        let conn = db.read()?;
+
+        let limit = limit.unwrap_or(20);
+
+        search_service::quick_switch(&conn, &keyword, limit).map_err(|e| {
+            error!("quick_switch 错误: {:?}", e);
             e.into()
         })
+    })
+    .await
 }
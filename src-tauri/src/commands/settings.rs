@@ -1,5 +1,8 @@
 use crate::db::get_db_path;
+use super::with_db;
 use crate::error::ApiError;
+use crate::services::trash_service;
+use log::{error, info};
 use tauri::AppHandle;
 
 /// 获取数据库路径
@@ -12,3 +15,30 @@ pub fn get_database_path(app: AppHandle) -> Result<String, ApiError> {
 
     Ok(path.to_string_lossy().to_string())
 }
+
+/// 获取回收站保留天数（0 表示不自动清理）
+#[tauri::command]
+pub async fn get_trash_retention_days(app: AppHandle) -> Result<i64, ApiError> {
+    with_db(app, move |db| {
+        let conn = db.read()?;
+
+        trash_service::get_retention_days(&conn).map_err(Into::into)
+    })
+    .await
+}
+
+/// 设置回收站保留天数，返回按新设置清理掉的条目数
+#[tauri::command]
+pub async fn set_trash_retention_days(days: i64, app: AppHandle) -> Result<usize, ApiError> {
+    info!("set_trash_retention_days 调用: days={}", days);
+
+    with_db(app, move |db| {
+        let conn = db.write()?;
+
+        trash_service::set_retention_days(&conn, days).map_err(|e| {
+            error!("set_trash_retention_days 错误: {:?}", e);
+            e.into()
+        })
+    })
+    .await
+}
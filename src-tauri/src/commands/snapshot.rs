@@ -0,0 +1,67 @@
+use super::{spawn_operation, with_db};
+use crate::db::get_db_path;
+use crate::error::ApiError;
+use crate::models::{OperationKind, SnapshotDto};
+use crate::services::snapshot_service;
+use crate::services::sync_service::{self, SyncScope};
+use log::{error, info};
+use tauri::AppHandle;
+
+/// 创建数据库快照
+#[tauri::command]
+pub async fn create_snapshot(name: String, app: AppHandle) -> Result<SnapshotDto, ApiError> {
+    info!("create_snapshot 调用: name={}", name);
+
+    let handle = app.clone();
+    with_db(app, move |db| {
+        let conn = db.read()?;
+
+        get_db_path(&handle)
+            .and_then(|path| snapshot_service::snapshot_dir(&path))
+            .and_then(|dir| snapshot_service::create_snapshot(&conn, &dir, &name, false))
+            .map_err(|e| {
+                error!("create_snapshot 错误: {:?}", e);
+                e.into()
+            })
+    })
+    .await
+}
+
+/// 列出数据库快照，最新的在前
+#[tauri::command]
+pub async fn list_snapshots(app: AppHandle) -> Result<Vec<SnapshotDto>, ApiError> {
+    let handle = app.clone();
+    with_db(app, move |_| {
+        get_db_path(&handle)
+            .and_then(|path| snapshot_service::snapshot_dir(&path))
+            .and_then(|dir| snapshot_service::list_snapshots(&dir))
+            .map_err(|e| {
+                error!("list_snapshots 错误: {:?}", e);
+                e.into()
+            })
+    })
+    .await
+}
+
+/// 从快照恢复（后台执行，覆盖现有数据），返回任务 ID，完成后发送 `operation-finished` 事件，
+/// 结果为恢复前自动创建的快照
+#[tauri::command]
+pub async fn restore_snapshot(fileName: String, app: AppHandle) -> Result<String, ApiError> {
+    info!("restore_snapshot 调用: fileName={}", fileName);
+
+    let handle = app.clone();
+    Ok(spawn_operation(app, OperationKind::SnapshotRestore, move |db| {
+        let mut conn = db.write()?;
+
+        get_db_path(&handle)
+            .and_then(|path| {
+                let dir = snapshot_service::snapshot_dir(&path)?;
+                snapshot_service::restore_snapshot(&mut conn, &path, &dir, &fileName)
+            })
+            .map_err(|e| {
+                error!("restore_snapshot 错误: {:?}", e);
+                e.into()
+            })
+            .inspect(|_| sync_service::spawn_sync(handle, SyncScope::All))
+    }))
+}
@@ -0,0 +1,81 @@
+use super::with_db;
+use crate::error::{ApiError, AppError};
+use crate::models::{SyncConflictDto, SyncResolution};
+use crate::repositories::sync_repository;
+use crate::services::sync_service;
+use log::{error, info};
+use tauri::AppHandle;
+
+/// 获取同步文件夹，未设置时返回 null
+#[tauri::command]
+pub async fn get_sync_folder(app: AppHandle) -> Result<Option<String>, ApiError> {
+    with_db(app, move |db| {
+        let conn = db.read()?;
+
+        sync_service::get_sync_folder(&conn).map_err(Into::into)
+    })
+    .await
+}
+
+/// 设置同步文件夹（传 null 关闭同步），完成首次比对后返回检测到的冲突
+#[tauri::command]
+pub async fn set_sync_folder(
+    path: Option<String>,
+    app: AppHandle,
+) -> Result<Vec<SyncConflictDto>, ApiError> {
+    info!("set_sync_folder 调用: path={:?}", path);
+
+    let handle = app.clone();
+    with_db(app, move |db| {
+        {
+            let conn = db.write()?;
+            sync_service::set_sync_folder(&conn, path.as_deref()).map_err(|e| {
+                error!("set_sync_folder 错误: {:?}", e);
+                ApiError::from(e)
+            })?;
+        }
+
+        sync_service::start(&handle).map_err(|e| {
+            error!("set_sync_folder 启动同步错误: {:?}", e);
+            e.into()
+        })
+    })
+    .await
+}
+
+/// 获取尚未解决的同步冲突
+#[tauri::command]
+pub async fn list_sync_conflicts(app: AppHandle) -> Result<Vec<SyncConflictDto>, ApiError> {
+    with_db(app, move |db| {
+        let conn = db.read()?;
+
+        sync_repository::list_conflicts(&conn).map_err(|e| {
+            error!("list_sync_conflicts 错误: {:?}", e);
+            e.into()
+        })
+    })
+    .await
+}
+
+/// 解决同步冲突：keep 为 database 时以数据库为准，为 file 时以文件为准
+#[tauri::command]
+pub async fn resolve_sync_conflict(
+    promptId: i64,
+    keep: SyncResolution,
+    app: AppHandle,
+) -> Result<(), ApiError> {
+    info!("resolve_sync_conflict 调用: promptId={}, keep={:?}", promptId, keep);
+
+    let root = sync_service::active_root(&app)
+        .ok_or_else(|| ApiError::from(AppError::ValidationError("未设置同步文件夹".to_string())))?;
+
+    with_db(app, move |db| {
+        let conn = db.write()?;
+
+        sync_service::resolve_conflict(&conn, &root, promptId, keep).map_err(|e| {
+            error!("resolve_sync_conflict 错误: {:?}", e);
+            e.into()
+        })
+    })
+    .await
+}
@@ -0,0 +1,72 @@
+use super::with_db;
+use crate::error::ApiError;
+use crate::models::TagDto;
+use crate::repositories::tag_repository;
+use crate::services::sync_service::{self, SyncScope};
+use log::{error, info};
+use tauri::AppHandle;
+
+/// 获取所有标签及使用次数
+#[tauri::command]
+pub async fn list_tags(app: AppHandle) -> Result<Vec<TagDto>, ApiError> {
+    with_db(app, move |db| {
+        let conn = db.read()?;
+
+        tag_repository::list_tags(&conn).map_err(|e| {
+            error!("list_tags 错误: {:?}", e);
+            e.into()
+        })
+    })
+    .await
+}
+
+/// 重命名标签
+#[tauri::command]
+pub async fn rename_tag(id: i64, name: String, app: AppHandle) -> Result<(), ApiError> {
+    info!("rename_tag 调用: id={}, name={}", id, name);
+
+    with_db(app.clone(), move |db| {
+        let conn = db.write()?;
+
+        tag_repository::rename_tag(&conn, id, &name).map_err(|e| {
+            error!("rename_tag 错误: {:?}", e);
+            e.into()
+        })
+    })
+    .await
+    .inspect(|_| sync_service::spawn_sync(app, SyncScope::All))
+}
+
+/// 将源标签合并到目标标签
+#[tauri::command]
+pub async fn merge_tags(sourceId: i64, targetId: i64, app: AppHandle) -> Result<(), ApiError> {
+    info!("merge_tags 调用: sourceId={}, targetId={}", sourceId, targetId);
+
+    with_db(app.clone(), move |db| {
+        let conn = db.write()?;
+
+        tag_repository::merge_tags(&conn, sourceId, targetId).map_err(|e| {
+            error!("merge_tags 错误: {:?}", e);
+            e.into()
+        })
+    })
+    .await
+    .inspect(|_| sync_service::spawn_sync(app, SyncScope::All))
+}
+
+/// 删除标签
+#[tauri::command]
+pub async fn delete_tag(id: i64, app: AppHandle) -> Result<(), ApiError> {
+    info!("delete_tag 调用: id={}", id);
+
+    with_db(app.clone(), move |db| {
+        let conn = db.write()?;
+
+        tag_repository::delete_tag(&conn, id).map_err(|e| {
+            error!("delete_tag 错误: {:?}", e);
+            e.into()
+        })
+    })
+    .await
+    .inspect(|_| sync_service::spawn_sync(app, SyncScope::All))
+}
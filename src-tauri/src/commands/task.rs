@@ -1,94 +1,105 @@
-use crate::db::DbState;
+use super::with_db;
 use crate::error::ApiError;
-use crate::models::TaskDto;
+use crate::models::{SortBy, TaskDto};
 use crate::repositories::task_repository;
+use crate::services::sync_service::{self, SyncScope};
 use log::{error, info};
-use tauri::State;
+use tauri::AppHandle;
 
 /// 获取项目下的任务
 #[tauri::command]
-pub fn list_tasks(projectId: i64, db: State<DbState>) -> Result<Vec<TaskDto>, ApiError> {
-    info!("list_tasks 调用: projectId={}", projectId);
-    
-    let conn = db.0.lock().map_err(|e| {
-        error!("获取数据库锁失败: {}", e);
-        ApiError {
-            code: "LOCK_ERROR".to_string(),
-            message: format!("获取数据库锁失败: {}", e),
-        }
-    })?;
+pub async fn list_tasks(
+    projectId: i64,
+    sortBy: Option<SortBy>,
+    app: AppHandle,
+) -> Result<Vec<TaskDto>, ApiError> {
+    info!("list_tasks 调用: projectId={}, sortBy={:?}", projectId, sortBy);
+
+    with_db(app, move |db| {
+        let conn = db.read()?;
 
-    task_repository::list_tasks_by_project(&conn, projectId).map_err(|e| {
-        error!("list_tasks 错误: {:?}", e);
-        e.into()
+        task_repository::list_tasks_by_project(&conn, projectId, sortBy.unwrap_or_default()).map_err(|e| {
+            error!("list_tasks 错误: {:?}", e);
+            e.into()
+        })
     })
+    .await
 }
 
 /// 创建任务
 #[tauri::command]
-pub fn create_task(
+pub async fn create_task(
     projectId: i64,
     name: String,
     description: Option<String>,
-    db: State<DbState>,
+    app: AppHandle,
 ) -> Result<TaskDto, ApiError> {
     info!("create_task 调用: projectId={}, name={}, description={:?}", projectId, name, description);
-    
-    let conn = db.0.lock().map_err(|e| {
-        error!("获取数据库锁失败: {}", e);
-        ApiError {
-            code: "LOCK_ERROR".to_string(),
-            message: format!("获取数据库锁失败: {}", e),
-        }
-    })?;
 
-    task_repository::create_task(&conn, projectId, &name, description.as_deref())
-        .map_err(|e| {
-            error!("create_task 错误: {:?}", e);
-            e.into()
-        })
+    with_db(app, move |db| {
+        let conn = db.write()?;
+
+        task_repository::create_task(&conn, projectId, &name, description.as_deref())
+            .map_err(|e| {
+                error!("create_task 错误: {:?}", e);
+                e.into()
+            })
+    })
+    .await
 }
 
 /// 更新任务
 #[tauri::command]
-pub fn update_task(
+pub async fn update_task(
     id: i64,
     name: Option<String>,
     description: Option<String>,
-    db: State<DbState>,
+    app: AppHandle,
 ) -> Result<(), ApiError> {
     info!("update_task 调用: id={}, name={:?}, description={:?}", id, name, description);
-    
-    let conn = db.0.lock().map_err(|e| {
-        error!("获取数据库锁失败: {}", e);
-        ApiError {
-            code: "LOCK_ERROR".to_string(),
-            message: format!("获取数据库锁失败: {}", e),
-        }
-    })?;
 
-    task_repository::update_task(&conn, id, name.as_deref(), description.as_deref())
-        .map_err(|e| {
-            error!("update_task 错误: {:?}", e);
-            e.into()
-        })
+    with_db(app.clone(), move |db| {
+        let conn = db.write()?;
+
+        task_repository::update_task(&conn, id, name.as_deref(), description.as_deref())
+            .map_err(|e| {
+                error!("update_task 错误: {:?}", e);
+                e.into()
+            })
+    })
+    .await
+    .inspect(|_| sync_service::spawn_sync(app, SyncScope::All))
 }
 
 /// 删除任务
 #[tauri::command]
-pub fn delete_task(id: i64, db: State<DbState>) -> Result<(), ApiError> {
+pub async fn delete_task(id: i64, app: AppHandle) -> Result<(), ApiError> {
     info!("delete_task 调用: id={}", id);
-    
-    let conn = db.0.lock().map_err(|e| {
-        error!("获取数据库锁失败: {}", e);
-        ApiError {
-            code: "LOCK_ERROR".to_string(),
-            message: format!("获取数据库锁失败: {}", e),
-        }
-    })?;
 
-    task_repository::delete_task(&conn, id).map_err(|e| {
-        error!("delete_task 错误: {:?}", e);
-        e.into()
+    with_db(app.clone(), move |db| {
+        let conn = db.write()?;
+
+        task_repository::delete_task(&conn, id).map_err(|e| {
+            error!("delete_task 错误: {:?}", e);
+            e.into()
+        })
+    })
+    .await
+    .inspect(|_| sync_service::spawn_sync(app, SyncScope::All))
+}
+
+/// 按全局唯一 ID 获取任务
+#[tauri::command]
+pub async fn get_task_by_uuid(uuid: String, app: AppHandle) -> Result<TaskDto, ApiError> {
+    info!("get_task_by_uuid 调用: uuid={}", uuid);
+
+    with_db(app, move |db| {
+        let conn = db.read()?;
+
+        task_repository::get_task_by_uuid(&conn, &uuid).map_err(|e| {
+            error!("get_task_by_uuid 错误: {:?}", e);
+            e.into()
+        })
     })
+    .await
 }
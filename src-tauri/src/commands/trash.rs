@@ -0,0 +1,66 @@
+use super::with_db;
+use crate::error::{ApiError, AppError};
+use crate::models::{TrashItemDto, TrashKind};
+use crate::repositories::trash_repository;
+use crate::services::sync_service::{self, SyncScope};
+use log::{error, info};
+use tauri::AppHandle;
+
+/// 获取回收站条目
+#[tauri::command]
+pub async fn list_trash(app: AppHandle) -> Result<Vec<TrashItemDto>, ApiError> {
+    info!("list_trash 调用");
+
+    with_db(app, move |db| {
+        let conn = db.read()?;
+
+        trash_repository::list_trash(&conn).map_err(|e| {
+            error!("list_trash 错误: {:?}", e);
+            e.into()
+        })
+    })
+    .await
+}
+
+/// 从回收站恢复条目
+#[tauri::command]
+pub async fn restore_item(kind: TrashKind, id: i64, app: AppHandle) -> Result<(), ApiError> {
+    info!("restore_item 调用: kind={:?}, id={}", kind, id);
+
+    with_db(app.clone(), move |db| {
+        let conn = db.write()?;
+
+        trash_repository::restore_item(&conn, kind, id).map_err(|e| {
+            error!("restore_item 错误: {:?}", e);
+            e.into()
+        })
+    })
+    .await
+    .inspect(|_| sync_service::spawn_sync(app, SyncScope::All))
+}
+
+/// 彻底删除回收站条目：同时指定 kind 和 id 时只删除该条目，都不指定时清空回收站
+#[tauri::command]
+pub async fn purge_trash(
+    kind: Option<TrashKind>,
+    id: Option<i64>,
+    app: AppHandle,
+) -> Result<usize, ApiError> {
+    info!("purge_trash 调用: kind={:?}, id={:?}", kind, id);
+
+    with_db(app, move |db| {
+        let conn = db.write()?;
+
+        let result = match (kind, id) {
+            (Some(kind), Some(id)) => trash_repository::purge_item(&conn, kind, id).map(|_| 1),
+            (None, None) => trash_repository::purge(&conn, None),
+            _ => Err(AppError::ValidationError("kind 和 id 需要同时指定".to_string())),
+        };
+
+        result.map_err(|e| {
+            error!("purge_trash 错误: {:?}", e);
+            e.into()
+        })
+    })
+    .await
+}
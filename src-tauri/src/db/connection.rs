@@ -1,11 +1,58 @@
-use crate::error::AppError;
+use super::migrations;
+use super::pool::{PooledConnection, ReadPool};
+use crate::error::{ApiError, AppError};
+use chrono::Utc;
+use log::error;
 use rusqlite::Connection;
-use std::path::PathBuf;
-use std::sync::Mutex;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
 use tauri::Manager;
 
+/// 读连接池大小
+const READ_POOL_SIZE: usize = 4;
+/// 数据库被占用时的等待时间
+pub const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// 数据库连接状态管理
-pub struct DbState(pub Mutex<Connection>);
+///
+/// 写操作通过唯一的写连接串行执行；读操作从只读连接池中取连接，可与写操作并发。
+pub struct DbState {
+    writer: Mutex<Connection>,
+    readers: ReadPool,
+}
+
+impl DbState {
+    /// 以已完成迁移的写连接创建状态，并为同一数据库文件打开读连接池
+    pub fn new(writer: Connection, db_path: &Path) -> Result<Self, AppError> {
+        Ok(DbState {
+            writer: Mutex::new(writer),
+            readers: ReadPool::open(db_path, READ_POOL_SIZE)?,
+        })
+    }
+
+    /// 获取只读连接
+    pub fn read(&self) -> Result<PooledConnection<'_>, ApiError> {
+        self.readers.get().map_err(|e| {
+            error!("获取数据库读连接失败: {}", e);
+            ApiError {
+                code: "LOCK_ERROR".to_string(),
+                message: format!("获取数据库读连接失败: {}", e),
+            }
+        })
+    }
+
+    /// 获取写连接（独占）
+    pub fn write(&self) -> Result<MutexGuard<'_, Connection>, ApiError> {
+        self.writer.lock().map_err(|e| {
+            error!("获取数据库锁失败: {}", e);
+            ApiError {
+                code: "LOCK_ERROR".to_string(),
+                message: format!("获取数据库锁失败: {}", e),
+            }
+        })
+    }
+}
 
 /// 获取数据库文件路径
 pub fn get_db_path(app: &tauri::AppHandle) -> Result<PathBuf, AppError> {
@@ -20,74 +67,62 @@ pub fn get_db_path(app: &tauri::AppHandle) -> Result<PathBuf, AppError> {
     Ok(app_data_dir.join("prompts.db"))
 }
 
-/// 建立数据库连接
+/// 建立数据库连接（读写）
 pub fn establish_connection(db_path: &PathBuf) -> Result<Connection, AppError> {
     let conn = Connection::open(db_path)?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
 
-    // 开启外键约束
-    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+    // 开启外键约束；使用 WAL 日志模式，使读连接不被写操作阻塞
+    conn.execute_batch(
+        "PRAGMA foreign_keys = ON;
+         PRAGMA journal_mode = WAL;
+         PRAGMA synchronous = NORMAL;",
+    )?;
 
     Ok(conn)
 }
 
-/// 初始化数据库表结构
-pub fn init_db(conn: &Connection) -> Result<(), AppError> {
-    // 创建 projects 表
-    conn.execute_batch(
-        r#"
-        CREATE TABLE IF NOT EXISTS projects (
-            id          INTEGER PRIMARY KEY AUTOINCREMENT,
-            name        TEXT NOT NULL,
-            created_at  TEXT NOT NULL,
-            updated_at  TEXT
-        );
-        "#,
-    )?;
+/// 初始化或升级数据库表结构
+pub fn init_db(conn: &mut Connection) -> Result<(), AppError> {
+    migrations::run_migrations(conn)
+}
 
-    // 创建 tasks 表
-    conn.execute_batch(
-        r#"
-        CREATE TABLE IF NOT EXISTS tasks (
-            id          INTEGER PRIMARY KEY AUTOINCREMENT,
-            project_id  INTEGER NOT NULL,
-            name        TEXT NOT NULL,
-            description TEXT,
-            created_at  TEXT NOT NULL,
-            updated_at  TEXT,
-            FOREIGN KEY(project_id) REFERENCES projects(id) ON DELETE CASCADE
-        );
-
-        CREATE INDEX IF NOT EXISTS idx_tasks_project_id ON tasks (project_id);
-        "#,
-    )?;
+/// 升级前备份数据库文件，返回备份文件路径
+///
+/// 使用 `VACUUM INTO` 生成一致的副本，WAL 文件中尚未写回的内容也会包含在内。
+pub fn backup_before_migration(
+    conn: &Connection,
+    db_path: &Path,
+    from_version: i64,
+) -> Result<PathBuf, AppError> {
+    let backup_dir = db_path
+        .parent()
+        .map(|p| p.join("migration-backups"))
+        .ok_or_else(|| AppError::Unknown("无法确定数据库所在目录".to_string()))?;
+    std::fs::create_dir_all(&backup_dir)?;
 
-    // 创建 prompt_entries 表
-    conn.execute_batch(
-        r#"
-        CREATE TABLE IF NOT EXISTS prompt_entries (
-            id          INTEGER PRIMARY KEY AUTOINCREMENT,
-            task_id     INTEGER NOT NULL,
-            title       TEXT,
-            content     TEXT NOT NULL,
-            tags        TEXT,
-            model       TEXT,
-            created_at  TEXT NOT NULL,
-            updated_at  TEXT,
-            FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE
-        );
-
-        CREATE INDEX IF NOT EXISTS idx_prompt_task_id ON prompt_entries (task_id);
-        CREATE INDEX IF NOT EXISTS idx_prompt_created_at ON prompt_entries (created_at);
-        "#,
-    )?;
+    let timestamp = Utc::now().format("%Y%m%d%H%M%S");
+    let backup_path = backup_dir.join(format!("prompts-v{}-{}.db", from_version, timestamp));
+    conn.execute("VACUUM INTO ?1", [backup_path.to_string_lossy()])?;
 
-    Ok(())
+    Ok(backup_path)
 }
 
 /// 初始化数据库（在应用启动时调用）
 pub fn setup_database(app: &tauri::AppHandle) -> Result<DbState, AppError> {
     let db_path = get_db_path(app)?;
-    let conn = establish_connection(&db_path)?;
-    init_db(&conn)?;
-    Ok(DbState(Mutex::new(conn)))
+    let mut conn = establish_connection(&db_path)?;
+
+    // 拒绝打开由更新版本应用创建的数据库
+    let current = migrations::ensure_supported(&conn)?;
+
+    // 已有数据且需要升级时，先备份原数据库
+    let needs_upgrade = current < migrations::latest_version();
+    if needs_upgrade && (current > 0 || migrations::has_existing_tables(&conn)?) {
+        let backup_path = backup_before_migration(&conn, &db_path, current)?;
+        log::info!("数据库升级前已备份至: {:?}", backup_path);
+    }
+
+    init_db(&mut conn)?;
+    DbState::new(conn, &db_path)
 }
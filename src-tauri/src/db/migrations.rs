@@ -0,0 +1,579 @@
+use crate::error::AppError;
+use crate::utils::id::new_uuid;
+use crate::utils::pinyin::pinyin_keys;
+use log::info;
+use rusqlite::{params, Connection};
+
+/// 单个迁移步骤
+pub struct Migration {
+    /// 迁移完成后的 schema 版本号（写入 PRAGMA user_version）
+    pub version: i64,
+    pub description: &'static str,
+    pub up: fn(&Connection) -> Result<(), AppError>,
+}
+
+/// 所有迁移，按版本号升序排列；已发布的迁移不可再修改，只能追加
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "初始表结构",
+        up: v1_initial_schema,
+    },
+    Migration {
+        version: 2,
+        description: "提示词全文索引 (FTS5)",
+        up: v2_prompt_fts,
+    },
+    Migration {
+        version: 3,
+        description: "全文索引改用 trigram 分词以支持中文",
+        up: v3_trigram_fts,
+    },
+    Migration {
+        version: 4,
+        description: "项目、任务名称及提示词标题的拼音检索列",
+        up: v4_pinyin_columns,
+    },
+    Migration {
+        version: 5,
+        description: "提示词修订历史",
+        up: v5_prompt_revisions,
+    },
+    Migration {
+        version: 6,
+        description: "回收站（软删除）与应用设置表",
+        up: v6_trash_and_settings,
+    },
+    Migration {
+        version: 7,
+        description: "标签独立成表 (tags / prompt_tags)",
+        up: v7_normalized_tags,
+    },
+    Migration {
+        version: 8,
+        description: "项目、任务和提示词的全局唯一 ID",
+        up: v8_uuid_columns,
+    },
+    Migration {
+        version: 9,
+        description: "同步文件夹状态表",
+        up: v9_sync_state,
+    },
+];
+
+/// 当前应用支持的最新 schema 版本
+pub fn latest_version() -> i64 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// 读取数据库当前的 schema 版本
+pub fn current_version(conn: &Connection) -> Result<i64, AppError> {
+    let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    Ok(version)
+}
+
+/// 数据库中是否已有业务表（用于区分全新数据库与未记录版本的旧数据库）
+pub fn has_existing_tables(conn: &Connection) -> Result<bool, AppError> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'projects'",
+        [],
+        |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
+
+/// 检查数据库版本是否可被当前应用打开
+pub fn ensure_supported(conn: &Connection) -> Result<i64, AppError> {
+    let current = current_version(conn)?;
+    let latest = latest_version();
+
+    if current > latest {
+        return Err(AppError::MigrationError(format!(
+            "数据库版本 {} 高于当前应用支持的版本 {}，请升级 PromptLog 后再打开",
+            current, latest
+        )));
+    }
+
+    Ok(current)
+}
+
+/// 依次执行所有未应用的迁移，每个迁移在独立事务中完成
+pub fn run_migrations(conn: &mut Connection) -> Result<(), AppError> {
+    let current = ensure_supported(conn)?;
+
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+        info!(
+            "执行数据库迁移 v{}: {}",
+            migration.version, migration.description
+        );
+
+        let tx = conn.transaction()?;
+        (migration.up)(&tx).map_err(|e| {
+            AppError::MigrationError(format!(
+                "迁移 v{} ({}) 失败: {}",
+                migration.version, migration.description, e
+            ))
+        })?;
+        tx.pragma_update(None, "user_version", migration.version)?;
+        tx.commit()?;
+    }
+
+    Ok(())
+}
+
+/// v1: 初始表结构（兼容未记录版本号的旧数据库，因此全部使用 IF NOT EXISTS）
+fn v1_initial_schema(conn: &Connection) -> Result<(), AppError> {
+    // 创建 projects 表
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS projects (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            name        TEXT NOT NULL,
+            created_at  TEXT NOT NULL,
+            updated_at  TEXT
+        );
+        "#,
+    )?;
+
+    // 创建 tasks 表
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS tasks (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            project_id  INTEGER NOT NULL,
+            name        TEXT NOT NULL,
+            description TEXT,
+            created_at  TEXT NOT NULL,
+            updated_at  TEXT,
+            FOREIGN KEY(project_id) REFERENCES projects(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_tasks_project_id ON tasks (project_id);
+        "#,
+    )?;
+
+    // 创建 prompt_entries 表
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS prompt_entries (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            task_id     INTEGER NOT NULL,
+            title       TEXT,
+            content     TEXT NOT NULL,
+            tags        TEXT,
+            model       TEXT,
+            created_at  TEXT NOT NULL,
+            updated_at  TEXT,
+            FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_prompt_task_id ON prompt_entries (task_id);
+        CREATE INDEX IF NOT EXISTS idx_prompt_created_at ON prompt_entries (created_at);
+        "#,
+    )?;
+
+    Ok(())
+}
+
+/// v2: 为提示词标题和内容建立 FTS5 全文索引，并通过触发器保持同步
+fn v2_prompt_fts(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE VIRTUAL TABLE prompt_entries_fts USING fts5(
+            title,
+            content,
+            content = 'prompt_entries',
+            content_rowid = 'id'
+        );
+
+        CREATE TRIGGER prompt_entries_fts_ai AFTER INSERT ON prompt_entries BEGIN
+            INSERT INTO prompt_entries_fts (rowid, title, content)
+            VALUES (new.id, new.title, new.content);
+        END;
+
+        CREATE TRIGGER prompt_entries_fts_ad AFTER DELETE ON prompt_entries BEGIN
+            INSERT INTO prompt_entries_fts (prompt_entries_fts, rowid, title, content)
+            VALUES ('delete', old.id, old.title, old.content);
+        END;
+
+        CREATE TRIGGER prompt_entries_fts_au AFTER UPDATE OF title, content ON prompt_entries BEGIN
+            INSERT INTO prompt_entries_fts (prompt_entries_fts, rowid, title, content)
+            VALUES ('delete', old.id, old.title, old.content);
+            INSERT INTO prompt_entries_fts (rowid, title, content)
+            VALUES (new.id, new.title, new.content);
+        END;
+
+        -- 为已有数据建立索引
+        INSERT INTO prompt_entries_fts (prompt_entries_fts) VALUES ('rebuild');
+        "#,
+    )?;
+
+    Ok(())
+}
+
+/// v3: 使用 trigram 分词重建全文索引
+///
+/// unicode61 分词会把连续的中文当成一个词，导致中文子串无法命中；
+/// trigram 按三字符滑窗建立索引，任意语言的子串都能匹配。
+fn v3_trigram_fts(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        DROP TRIGGER IF EXISTS prompt_entries_fts_ai;
+        DROP TRIGGER IF EXISTS prompt_entries_fts_ad;
+        DROP TRIGGER IF EXISTS prompt_entries_fts_au;
+        DROP TABLE IF EXISTS prompt_entries_fts;
+
+        CREATE VIRTUAL TABLE prompt_entries_fts USING fts5(
+            title,
+            content,
+            content = 'prompt_entries',
+            content_rowid = 'id',
+            tokenize = 'trigram case_sensitive 0'
+        );
+
+        CREATE TRIGGER prompt_entries_fts_ai AFTER INSERT ON prompt_entries BEGIN
+            INSERT INTO prompt_entries_fts (rowid, title, content)
+            VALUES (new.id, new.title, new.content);
+        END;
+
+        CREATE TRIGGER prompt_entries_fts_ad AFTER DELETE ON prompt_entries BEGIN
+            INSERT INTO prompt_entries_fts (prompt_entries_fts, rowid, title, content)
+            VALUES ('delete', old.id, old.title, old.content);
+        END;
+
+        CREATE TRIGGER prompt_entries_fts_au AFTER UPDATE OF title, content ON prompt_entries BEGIN
+            INSERT INTO prompt_entries_fts (prompt_entries_fts, rowid, title, content)
+            VALUES ('delete', old.id, old.title, old.content);
+            INSERT INTO prompt_entries_fts (rowid, title, content)
+            VALUES (new.id, new.title, new.content);
+        END;
+
+        INSERT INTO prompt_entries_fts (prompt_entries_fts) VALUES ('rebuild');
+        "#,
+    )?;
+
+    Ok(())
+}
+
+/// v4: 增加拼音检索列并为已有数据回填
+fn v4_pinyin_columns(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE projects ADD COLUMN name_pinyin TEXT NOT NULL DEFAULT '';
+        ALTER TABLE projects ADD COLUMN name_initials TEXT NOT NULL DEFAULT '';
+        ALTER TABLE tasks ADD COLUMN name_pinyin TEXT NOT NULL DEFAULT '';
+        ALTER TABLE tasks ADD COLUMN name_initials TEXT NOT NULL DEFAULT '';
+        ALTER TABLE prompt_entries ADD COLUMN title_pinyin TEXT NOT NULL DEFAULT '';
+        ALTER TABLE prompt_entries ADD COLUMN title_initials TEXT NOT NULL DEFAULT '';
+
+        CREATE INDEX idx_projects_name_pinyin ON projects (name_pinyin);
+        CREATE INDEX idx_tasks_name_pinyin ON tasks (project_id, name_pinyin);
+        "#,
+    )?;
+
+    backfill_pinyin(conn, "projects", "name", "name_pinyin", "name_initials")?;
+    backfill_pinyin(conn, "tasks", "name", "name_pinyin", "name_initials")?;
+    backfill_pinyin(conn, "prompt_entries", "title", "title_pinyin", "title_initials")?;
+
+    Ok(())
+}
+
+/// 为指定表的文本列计算拼音检索键
+fn backfill_pinyin(
+    conn: &Connection,
+    table: &str,
+    source: &str,
+    full_column: &str,
+    initials_column: &str,
+) -> Result<(), AppError> {
+    let rows: Vec<(i64, Option<String>)> = conn
+        .prepare(&format!("SELECT id, {} FROM {}", source, table))?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut update = conn.prepare(&format!(
+        "UPDATE {} SET {} = ?1, {} = ?2 WHERE id = ?3",
+        table, full_column, initials_column
+    ))?;
+
+    for (id, text) in rows {
+        let keys = pinyin_keys(text.as_deref().unwrap_or(""));
+        update.execute(params![keys.full, keys.initials, id])?;
+    }
+
+    Ok(())
+}
+
+/// v5: 提示词修订历史表，每次更新前保存旧版本
+fn v5_prompt_revisions(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE prompt_revisions (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            prompt_id   INTEGER NOT NULL,
+            title       TEXT,
+            content     TEXT NOT NULL,
+            tags        TEXT,
+            model       TEXT,
+            version_at  TEXT NOT NULL,
+            created_at  TEXT NOT NULL,
+            FOREIGN KEY(prompt_id) REFERENCES prompt_entries(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX idx_prompt_revisions_prompt_id ON prompt_revisions (prompt_id);
+        "#,
+    )?;
+
+    Ok(())
+}
+
+/// v6: 为三张业务表增加软删除标记，并新增键值形式的应用设置表
+fn v6_trash_and_settings(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        ALTER TABLE projects ADD COLUMN deleted_at TEXT;
+        ALTER TABLE tasks ADD COLUMN deleted_at TEXT;
+        ALTER TABLE prompt_entries ADD COLUMN deleted_at TEXT;
+
+        CREATE INDEX idx_projects_deleted_at ON projects (deleted_at);
+        CREATE INDEX idx_tasks_deleted_at ON tasks (deleted_at);
+        CREATE INDEX idx_prompt_deleted_at ON prompt_entries (deleted_at);
+
+        CREATE TABLE settings (
+            key         TEXT PRIMARY KEY,
+            value       TEXT NOT NULL,
+            updated_at  TEXT NOT NULL
+        );
+        "#,
+    )?;
+
+    Ok(())
+}
+
+/// v7: 将 prompt_entries.tags 中的 JSON 标签拆分到 tags / prompt_tags 表
+fn v7_normalized_tags(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE tags (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            name        TEXT NOT NULL UNIQUE,
+            created_at  TEXT NOT NULL
+        );
+
+        CREATE TABLE prompt_tags (
+            prompt_id   INTEGER NOT NULL,
+            tag_id      INTEGER NOT NULL,
+            position    INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (prompt_id, tag_id),
+            FOREIGN KEY(prompt_id) REFERENCES prompt_entries(id) ON DELETE CASCADE,
+            FOREIGN KEY(tag_id) REFERENCES tags(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX idx_prompt_tags_tag_id ON prompt_tags (tag_id);
+        "#,
+    )?;
+
+    let rows: Vec<(i64, String)> = conn
+        .prepare("SELECT id, tags FROM prompt_entries WHERE tags IS NOT NULL")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut insert_tag = conn.prepare("INSERT OR IGNORE INTO tags (name, created_at) VALUES (?1, ?2)")?;
+    let mut insert_link = conn.prepare(
+        "INSERT OR IGNORE INTO prompt_tags (prompt_id, tag_id, position)
+         SELECT ?1, id, ?3 FROM tags WHERE name = ?2",
+    )?;
+
+    for (prompt_id, tags_json) in rows {
+        // 无法解析的旧数据直接忽略，与旧版本读取时的行为一致
+        let tags: Vec<String> = serde_json::from_str(&tags_json).unwrap_or_default();
+        let mut position = 0_i64;
+        for name in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+            insert_tag.execute(params![name, now])?;
+            position += insert_link.execute(params![prompt_id, name, position])? as i64;
+        }
+    }
+
+    conn.execute_batch("ALTER TABLE prompt_entries DROP COLUMN tags;")?;
+
+    Ok(())
+}
+
+/// v8: 为项目、任务和提示词增加 uuid 列并为已有数据生成
+fn v8_uuid_columns(conn: &Connection) -> Result<(), AppError> {
+    for table in ["projects", "tasks", "prompt_entries"] {
+        conn.execute_batch(&format!("ALTER TABLE {} ADD COLUMN uuid TEXT;", table))?;
+
+        let ids: Vec<i64> = conn
+            .prepare(&format!("SELECT id FROM {}", table))?
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut update = conn.prepare(&format!("UPDATE {} SET uuid = ?1 WHERE id = ?2", table))?;
+        for id in ids {
+            update.execute(params![new_uuid(), id])?;
+        }
+
+        conn.execute_batch(&format!(
+            "CREATE UNIQUE INDEX idx_{0}_uuid ON {0} (uuid);",
+            table
+        ))?;
+    }
+
+    Ok(())
+}
+
+/// v9: 同步文件夹中每个提示词文件的同步状态
+fn v9_sync_state(conn: &Connection) -> Result<(), AppError> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE sync_state (
+            prompt_id     INTEGER PRIMARY KEY,
+            path          TEXT NOT NULL UNIQUE,
+            content_hash  TEXT NOT NULL,
+            db_version    TEXT NOT NULL,
+            conflict      TEXT,
+            conflict_at   TEXT,
+            synced_at     TEXT NOT NULL,
+            FOREIGN KEY(prompt_id) REFERENCES prompt_entries(id) ON DELETE CASCADE
+        );
+        "#,
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 未记录版本号的旧数据库（v1 之前的表结构），标签以 JSON 存在 prompt_entries.tags 中
+    fn baseline_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            r#"
+            PRAGMA foreign_keys = ON;
+
+            CREATE TABLE projects (
+                id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                name        TEXT NOT NULL,
+                created_at  TEXT NOT NULL,
+                updated_at  TEXT
+            );
+            CREATE TABLE tasks (
+                id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                project_id  INTEGER NOT NULL,
+                name        TEXT NOT NULL,
+                description TEXT,
+                created_at  TEXT NOT NULL,
+                updated_at  TEXT,
+                FOREIGN KEY(project_id) REFERENCES projects(id) ON DELETE CASCADE
+            );
+            CREATE TABLE prompt_entries (
+                id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                task_id     INTEGER NOT NULL,
+                title       TEXT,
+                content     TEXT NOT NULL,
+                tags        TEXT,
+                model       TEXT,
+                created_at  TEXT NOT NULL,
+                updated_at  TEXT,
+                FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE
+            );
+
+            INSERT INTO projects (name, created_at) VALUES ('用户', '2024-01-01T00:00:00Z');
+            INSERT INTO tasks (project_id, name, created_at) VALUES (1, '数据清洗', '2024-01-01T00:00:00Z');
+            INSERT INTO prompt_entries (task_id, title, content, tags, created_at)
+                VALUES (1, '第一条', 'content 1', '["写作", " ", "en", "写作"]', '2024-01-01T00:00:00Z');
+            INSERT INTO prompt_entries (task_id, title, content, tags, created_at)
+                VALUES (1, NULL, 'content 2', 'not json', '2024-01-02T00:00:00Z');
+            INSERT INTO prompt_entries (task_id, title, content, tags, created_at)
+                VALUES (1, NULL, 'content 3', NULL, '2024-01-03T00:00:00Z');
+            "#,
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn baseline_database_migrates_to_latest() {
+        let mut conn = baseline_db();
+        assert!(has_existing_tables(&conn).unwrap());
+        assert_eq!(current_version(&conn).unwrap(), 0);
+
+        run_migrations(&mut conn).unwrap();
+        assert_eq!(current_version(&conn).unwrap(), latest_version());
+
+        // 再次执行不会重复迁移
+        run_migrations(&mut conn).unwrap();
+        assert_eq!(current_version(&conn).unwrap(), latest_version());
+    }
+
+    #[test]
+    fn v7_moves_json_tags_to_prompt_tags() {
+        let mut conn = baseline_db();
+        run_migrations(&mut conn).unwrap();
+
+        let tags: Vec<String> = conn
+            .prepare(
+                "SELECT t.name FROM prompt_tags pt JOIN tags t ON pt.tag_id = t.id
+                 WHERE pt.prompt_id = 1 ORDER BY pt.position",
+            )
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(tags, vec!["写作".to_string(), "en".to_string()]);
+
+        // 无法解析的旧标签被忽略，tags 列已删除
+        let links: i64 = conn
+            .query_row("SELECT COUNT(*) FROM prompt_tags WHERE prompt_id <> 1", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(links, 0);
+        assert!(conn.prepare("SELECT tags FROM prompt_entries").is_err());
+    }
+
+    #[test]
+    fn v8_fills_unique_uuids() {
+        let mut conn = baseline_db();
+        run_migrations(&mut conn).unwrap();
+
+        for table in ["projects", "tasks", "prompt_entries"] {
+            let (total, distinct): (i64, i64) = conn
+                .query_row(
+                    &format!("SELECT COUNT(*), COUNT(DISTINCT uuid) FROM {} WHERE uuid IS NOT NULL", table),
+                    [],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .unwrap();
+            let rows: i64 = conn
+                .query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0))
+                .unwrap();
+            assert_eq!((total, distinct), (rows, rows), "{}", table);
+        }
+    }
+
+    #[test]
+    fn v4_backfills_pinyin() {
+        let mut conn = baseline_db();
+        run_migrations(&mut conn).unwrap();
+
+        let initials: String = conn
+            .query_row("SELECT name_initials FROM projects WHERE id = 1", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(initials, "yh");
+    }
+
+    #[test]
+    fn newer_database_is_rejected() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        run_migrations(&mut conn).unwrap();
+        conn.pragma_update(None, "user_version", latest_version() + 1).unwrap();
+
+        assert!(matches!(ensure_supported(&conn), Err(AppError::MigrationError(_))));
+        assert!(matches!(run_migrations(&mut conn), Err(AppError::MigrationError(_))));
+        assert_eq!(current_version(&conn).unwrap(), latest_version() + 1);
+    }
+}
@@ -1,4 +1,6 @@
 // 数据库模块
 mod connection;
+pub mod migrations;
+mod pool;
 
 pub use connection::*;
@@ -0,0 +1,91 @@
+use crate::error::AppError;
+use rusqlite::{Connection, OpenFlags};
+use std::ops::Deref;
+use std::path::Path;
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+
+/// 只读连接池
+///
+/// WAL 模式下读连接之间、读连接与写连接之间互不阻塞，
+/// 因此导出等长时间读取不会卡住列表和搜索。
+pub struct ReadPool {
+    idle: Mutex<Vec<Connection>>,
+    available: Condvar,
+}
+
+/// 从连接池借出的连接，离开作用域时自动归还
+pub struct PooledConnection<'a> {
+    pool: &'a ReadPool,
+    conn: Option<Connection>,
+}
+
+/// 等待空闲连接的最长时间
+const ACQUIRE_TIMEOUT: Duration = Duration::from_secs(30);
+
+impl ReadPool {
+    /// 打开 `size` 个只读连接
+    pub fn open(db_path: &Path, size: usize) -> Result<Self, AppError> {
+        let mut conns = Vec::with_capacity(size);
+        for _ in 0..size {
+            conns.push(open_reader(db_path)?);
+        }
+
+        Ok(ReadPool {
+            idle: Mutex::new(conns),
+            available: Condvar::new(),
+        })
+    }
+
+    /// 借出一个连接，池中没有空闲连接时等待
+    pub fn get(&self) -> Result<PooledConnection<'_>, AppError> {
+        let idle = self
+            .idle
+            .lock()
+            .map_err(|e| AppError::Unknown(format!("读连接池锁异常: {}", e)))?;
+
+        let (mut idle, timeout) = self
+            .available
+            .wait_timeout_while(idle, ACQUIRE_TIMEOUT, |idle| idle.is_empty())
+            .map_err(|e| AppError::Unknown(format!("读连接池锁异常: {}", e)))?;
+
+        if timeout.timed_out() && idle.is_empty() {
+            return Err(AppError::Unknown("等待数据库读连接超时".to_string()));
+        }
+
+        Ok(PooledConnection {
+            pool: self,
+            conn: idle.pop(),
+        })
+    }
+}
+
+impl Deref for PooledConnection<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn.as_ref().expect("连接已归还")
+    }
+}
+
+impl Drop for PooledConnection<'_> {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            if let Ok(mut idle) = self.pool.idle.lock() {
+                idle.push(conn);
+                self.pool.available.notify_one();
+            }
+        }
+    }
+}
+
+/// 打开只读连接
+fn open_reader(db_path: &Path) -> Result<Connection, AppError> {
+    let conn = Connection::open_with_flags(
+        db_path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX | OpenFlags::SQLITE_OPEN_URI,
+    )?;
+    conn.busy_timeout(super::BUSY_TIMEOUT)?;
+
+    Ok(conn)
+}
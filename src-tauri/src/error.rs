@@ -16,9 +16,30 @@ pub enum AppError {
     #[error("验证错误: {0}")]
     ValidationError(String),
     
+    #[error("迁移错误: {0}")]
+    MigrationError(String),
+    
     #[error("JSON 错误: {0}")]
     JsonError(#[from] serde_json::Error),
     
+    #[error("YAML 错误: {0}")]
+    YamlError(#[from] serde_yaml::Error),
+    
+    #[error("CSV 错误: {0}")]
+    CsvError(#[from] csv::Error),
+    
+    #[error("需要口令")]
+    PassphraseRequired,
+    
+    #[error("口令错误")]
+    WrongPassphrase,
+    
+    #[error("备份文件已损坏: {0}")]
+    BackupCorrupted(String),
+    
+    #[error("同步错误: {0}")]
+    SyncError(String),
+    
     #[error("未知错误: {0}")]
     Unknown(String),
 }
@@ -49,10 +70,38 @@ impl From<AppError> for ApiError {
                 code: "VALIDATION_ERROR".to_string(),
                 message: msg,
             },
+            AppError::MigrationError(msg) => ApiError {
+                code: "MIGRATION_ERROR".to_string(),
+                message: msg,
+            },
             AppError::JsonError(e) => ApiError {
                 code: "JSON_ERROR".to_string(),
                 message: format!("JSON 处理失败: {}", e),
             },
+            AppError::YamlError(e) => ApiError {
+                code: "YAML_ERROR".to_string(),
+                message: format!("YAML 处理失败: {}", e),
+            },
+            AppError::CsvError(e) => ApiError {
+                code: "CSV_ERROR".to_string(),
+                message: format!("CSV 处理失败: {}", e),
+            },
+            AppError::PassphraseRequired => ApiError {
+                code: "PASSPHRASE_REQUIRED".to_string(),
+                message: "备份文件已加密，请输入口令".to_string(),
+            },
+            AppError::WrongPassphrase => ApiError {
+                code: "WRONG_PASSPHRASE".to_string(),
+                message: "口令错误，无法解密备份文件".to_string(),
+            },
+            AppError::BackupCorrupted(msg) => ApiError {
+                code: "BACKUP_CORRUPTED".to_string(),
+                message: format!("备份文件已损坏或被篡改: {}", msg),
+            },
+            AppError::SyncError(msg) => ApiError {
+                code: "SYNC_ERROR".to_string(),
+                message: msg,
+            },
             AppError::Unknown(msg) => ApiError {
                 code: "UNKNOWN".to_string(),
                 message: msg,
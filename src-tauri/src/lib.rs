@@ -5,9 +5,12 @@ mod error;
 mod models;
 mod repositories;
 mod services;
+mod utils;
 
 use commands::*;
 use db::setup_database;
+use services::auto_backup_service;
+use services::sync_service::{self, SyncHandle};
 use log::LevelFilter;
 use simplelog::{CombinedLogger, Config, WriteLogger};
 use std::fs::File;
@@ -50,7 +53,20 @@ pub fn run() {
                     panic!("数据库初始化失败: {:?}", e);
                 }
             };
+
             app.manage(db_state);
+            app.manage(SyncHandle::default());
+
+            // 启动同步文件夹监听（首次比对可能较慢，放到后台）
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                if let Err(e) = sync_service::start(&handle) {
+                    log::error!("启动同步文件夹失败: {:?}", e);
+                }
+            });
+
+            // 启动定时自动备份和回收站清理
+            auto_backup_service::start_scheduler(app.handle().clone());
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -59,24 +75,75 @@ pub fn run() {
             create_project,
             update_project,
             delete_project,
+            get_project_by_uuid,
             // 任务命令
             list_tasks,
             create_task,
             update_task,
             delete_task,
+            get_task_by_uuid,
             // 提示词命令
             list_prompt_entries,
             create_prompt_entry,
             update_prompt_entry,
             delete_prompt_entry,
+            get_prompt_entry_by_uuid,
+            // 修订历史命令
+            list_prompt_revisions,
+            get_prompt_revision,
+            restore_prompt_revision,
+            prune_prompt_revisions,
+            // 标签命令
+            list_tags,
+            rename_tag,
+            merge_tags,
+            delete_tag,
             // 搜索命令
             search_prompt_entries,
+            quick_switch,
             // 设置命令
             get_database_path,
+            get_trash_retention_days,
+            set_trash_retention_days,
+            // 回收站命令
+            list_trash,
+            restore_item,
+            purge_trash,
             // 备份命令
             export_data,
             import_data,
+            preview_import,
+            get_export_schema,
+            export_markdown,
+            import_markdown,
+            export_site,
+            export_csv,
+            preview_csv,
+            import_csv,
+            import_chatgpt,
+            import_claude,
+            // 同步文件夹命令
+            get_sync_folder,
+            set_sync_folder,
+            list_sync_conflicts,
+            resolve_sync_conflict,
+            // 自动备份命令
+            get_backup_settings,
+            set_backup_settings,
+            list_backups,
+            create_backup,
+            restore_backup,
+            // 快照命令
+            create_snapshot,
+            list_snapshots,
+            restore_snapshot,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|app, event| {
+            // 退出前备份一次
+            if let tauri::RunEvent::Exit = event {
+                auto_backup_service::backup_on_exit(app);
+            }
+        });
 }
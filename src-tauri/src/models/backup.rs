@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// 自动备份设置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupSettings {
+    pub enabled: bool,
+    /// 两次自动备份之间的间隔（小时）
+    pub interval_hours: u32,
+    /// 备份目录，为空时使用应用数据目录下的 backups/
+    #[serde(default)]
+    pub folder: Option<String>,
+}
+
+/// 备份文件信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupFileDto {
+    pub file_name: String,
+    pub path: String,
+    /// 由文件名中的时间戳解析得到
+    pub created_at: String,
+    pub size: u64,
+}
+
+/// 导出范围：选中的项目、任务和提示词的并集
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportScope {
+    #[serde(default)]
+    pub project_ids: Vec<i64>,
+    #[serde(default)]
+    pub task_ids: Vec<i64>,
+    /// 单独选中的提示词，连同所属的任务和项目一起导出
+    #[serde(default)]
+    pub prompt_ids: Vec<i64>,
+}
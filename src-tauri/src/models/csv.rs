@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+/// CSV 列映射：每个字段对应的列序号（从 0 开始），未映射的字段为空
+///
+/// 只有内容列是必需的；没有项目或任务列时使用默认名称。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CsvColumnMapping {
+    #[serde(default)]
+    pub project: Option<usize>,
+    #[serde(default)]
+    pub task: Option<usize>,
+    #[serde(default)]
+    pub title: Option<usize>,
+    pub content: Option<usize>,
+    #[serde(default)]
+    pub tags: Option<usize>,
+    #[serde(default)]
+    pub model: Option<usize>,
+    #[serde(default)]
+    pub created_at: Option<usize>,
+    #[serde(default)]
+    pub updated_at: Option<usize>,
+    #[serde(default)]
+    pub uuid: Option<usize>,
+    /// 没有项目列或该列为空时使用的项目名
+    #[serde(default)]
+    pub default_project: Option<String>,
+    /// 没有任务列或该列为空时使用的任务名
+    #[serde(default)]
+    pub default_task: Option<String>,
+}
+
+/// CSV 预览：表头、前几行数据和按表头推测的列映射
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvPreview {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    /// 总数据行数（不含表头）
+    pub total_rows: usize,
+    pub suggested_mapping: CsvColumnMapping,
+}
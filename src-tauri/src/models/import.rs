@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+
+/// 导入模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    /// 清空现有数据后导入（默认）
+    #[default]
+    Replace,
+    /// 合并到现有数据中
+    Merge,
+}
+
+/// 合并导入时，遇到内容相同的提示词的处理策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    /// 保留现有记录，跳过导入的记录（默认）
+    #[default]
+    Skip,
+    /// 用导入的标题、标签和模型覆盖现有记录（旧版本写入修订历史）
+    Overwrite,
+    /// 两条都保留
+    KeepBoth,
+}
+
+/// 导入目标类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportTargetKind {
+    /// 备份中的任务并入该项目
+    Project,
+    /// 备份中的提示词并入该任务
+    Task,
+}
+
+/// 导入目标：将备份内容并入一个已有的项目或任务，而不是按备份中的项目结构导入
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportTarget {
+    pub kind: ImportTargetKind,
+    pub id: i64,
+}
+
+/// 导入结果统计
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub projects_added: usize,
+    /// 按名称匹配到已有项目的数量
+    pub projects_merged: usize,
+    pub tasks_added: usize,
+    /// 按名称匹配到已有任务的数量
+    pub tasks_merged: usize,
+    pub prompts_added: usize,
+    pub prompts_updated: usize,
+    pub prompts_skipped: usize,
+    /// 无法解析而被跳过的文件
+    #[serde(default)]
+    pub failed_files: Vec<ImportFailure>,
+}
+
+/// 导入时无法解析的文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportFailure {
+    pub path: String,
+    pub reason: String,
+}
+
+/// 导入预览：解析备份文件得到的摘要，以及与当前数据的差异，不写入数据库
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportPreview {
+    /// 备份文件的导出格式版本
+    pub version: i32,
+    pub exported_at: String,
+    pub project_count: usize,
+    pub task_count: usize,
+    pub prompt_count: usize,
+    /// 提示词创建时间的范围
+    pub earliest_at: Option<String>,
+    pub latest_at: Option<String>,
+    pub projects: Vec<ProjectPreview>,
+    pub diff: ImportDiff,
+}
+
+/// 备份中单个项目的统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectPreview {
+    pub name: String,
+    pub prompt_count: usize,
+    pub tasks: Vec<TaskPreview>,
+}
+
+/// 备份中单个任务的统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskPreview {
+    pub name: String,
+    pub prompt_count: usize,
+}
+
+/// 备份与当前数据的差异
+///
+/// removed 为当前有而备份中没有的条目，只在覆盖导入时才会真正删除。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportDiff {
+    pub projects: DiffCounts,
+    pub tasks: DiffCounts,
+    pub prompts: DiffCounts,
+    /// 有差异的提示词明细（数量有上限）
+    pub items: Vec<DiffItem>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiffCounts {
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+    pub unchanged: usize,
+}
+
+/// 差异类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffChange {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// 单条提示词差异
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffItem {
+    pub change: DiffChange,
+    pub project: String,
+    pub task: String,
+    pub title: Option<String>,
+    pub uuid: Option<String>,
+}
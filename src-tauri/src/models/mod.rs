@@ -2,9 +2,29 @@
 mod project;
 mod task;
 mod prompt;
+mod backup;
+mod csv;
+mod import;
+mod operation;
+mod revision;
 mod search;
+mod snapshot;
+mod sort;
+mod sync;
+mod tag;
+mod trash;
 
 pub use project::*;
 pub use task::*;
 pub use prompt::*;
+pub use backup::*;
+pub use csv::*;
+pub use import::*;
+pub use operation::*;
+pub use revision::*;
 pub use search::*;
+pub use snapshot::*;
+pub use sort::*;
+pub use sync::*;
+pub use tag::*;
+pub use trash::*;
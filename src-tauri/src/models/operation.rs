@@ -0,0 +1,33 @@
+use crate::error::ApiError;
+use serde::{Deserialize, Serialize};
+
+/// 后台长任务完成时发送的事件名
+pub const OPERATION_FINISHED_EVENT: &str = "operation-finished";
+
+/// 后台长任务类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationKind {
+    Export,
+    Import,
+    MarkdownExport,
+    MarkdownImport,
+    SiteExport,
+    CsvExport,
+    CsvImport,
+    ChatgptImport,
+    ClaudeImport,
+    BackupRestore,
+    SnapshotRestore,
+}
+
+/// 后台长任务完成事件
+#[derive(Debug, Clone, Serialize)]
+pub struct OperationFinishedEvent {
+    pub operation_id: String,
+    pub kind: OperationKind,
+    pub success: bool,
+    /// 任务的返回值，失败时为空
+    pub result: Option<serde_json::Value>,
+    pub error: Option<ApiError>,
+}
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PromptEntryDto {
     pub id: i64,
+    /// 全局唯一 ID，跨设备、导入导出时保持不变
+    pub uuid: String,
     pub task_id: i64,
     pub title: Option<String>,
     pub content: String,
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// 提示词修订记录数据传输对象
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptRevisionDto {
+    pub id: i64,
+    pub prompt_id: i64,
+    pub title: Option<String>,
+    pub content: String,
+    pub tags: Option<Vec<String>>,
+    pub model: Option<String>,
+    /// 该版本内容最后一次编辑的时间
+    pub version_at: String,
+    /// 该版本被新内容替换（即修订记录生成）的时间
+    pub created_at: String,
+}
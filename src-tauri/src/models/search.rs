@@ -9,5 +9,23 @@ pub struct SearchResultDto {
     pub project_name: String,
     pub task_name: String,
     pub snippet: String,
+    /// 摘要中实际命中的原文片段，用于高亮
+    pub highlight: Option<String>,
     pub created_at: String,
 }
+
+/// 快速切换结果数据传输对象
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuickSwitchItemDto {
+    /// 条目类型: "project" | "task" | "prompt"
+    pub kind: String,
+    pub id: i64,
+    pub project_id: i64,
+    pub task_id: Option<i64>,
+    /// 项目名、任务名或提示词标题
+    pub name: String,
+    /// 所属路径，如 "项目 / 任务"
+    pub context: Option<String>,
+    /// 名称中被命中的片段（包括拼音命中的汉字），用于高亮
+    pub highlight: Option<String>,
+}
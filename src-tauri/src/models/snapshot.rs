@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// 数据库快照信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotDto {
+    pub file_name: String,
+    /// 创建时指定的名称
+    pub name: String,
+    /// 由文件名中的时间戳解析得到
+    pub created_at: String,
+    pub size: u64,
+    /// 导入、恢复前自动创建的快照
+    pub automatic: bool,
+}
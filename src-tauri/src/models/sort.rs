@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// 名称列表的排序方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortBy {
+    /// 按创建时间倒序（默认）
+    #[default]
+    CreatedAt,
+    /// 按名称拼音顺序
+    Pinyin,
+}
+
+impl SortBy {
+    /// 对应的 ORDER BY 子句，`name_pinyin` 为各表的拼音检索列
+    pub fn order_clause(self) -> &'static str {
+        match self {
+            SortBy::CreatedAt => "ORDER BY created_at DESC",
+            SortBy::Pinyin => "ORDER BY name_pinyin COLLATE NOCASE, name",
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// 同步冲突：数据库和文件都发生了修改，需要用户选择保留哪一边
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncConflictDto {
+    pub prompt_id: i64,
+    pub title: Option<String>,
+    /// 相对于同步文件夹的路径
+    pub path: String,
+    pub reason: String,
+    pub detected_at: String,
+}
+
+/// 解决同步冲突时保留的一方
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncResolution {
+    /// 以数据库为准，覆盖文件
+    Database,
+    /// 以文件为准，写回数据库
+    File,
+}
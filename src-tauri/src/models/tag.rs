@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// 标签数据传输对象
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagDto {
+    pub id: i64,
+    pub name: String,
+    /// 使用该标签的提示词数量（不含回收站中的记录）
+    pub usage_count: i64,
+}
+
+/// 按多个标签筛选时的匹配方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TagMatchMode {
+    /// 命中任意一个标签（默认）
+    #[default]
+    Any,
+    /// 必须包含全部标签
+    All,
+}
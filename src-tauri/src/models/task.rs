@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskDto {
     pub id: i64,
+    /// 全局唯一 ID，跨设备、导入导出时保持不变
+    pub uuid: String,
     pub project_id: i64,
     pub name: String,
     pub description: Option<String>,
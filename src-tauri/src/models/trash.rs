@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+/// 回收站条目类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrashKind {
+    Project,
+    Task,
+    Prompt,
+}
+
+/// 回收站条目数据传输对象
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashItemDto {
+    pub kind: TrashKind,
+    pub id: i64,
+    /// 项目名、任务名，或提示词标题（无标题时取内容开头）
+    pub name: String,
+    /// 所属路径，如 "项目 / 任务"
+    pub context: Option<String>,
+    pub deleted_at: String,
+}
@@ -2,3 +2,8 @@
 pub mod project_repository;
 pub mod task_repository;
 pub mod prompt_repository;
+pub mod revision_repository;
+pub mod tag_repository;
+pub mod settings_repository;
+pub mod sync_repository;
+pub mod trash_repository;
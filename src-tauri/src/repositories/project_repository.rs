@@ -1,18 +1,22 @@
 use crate::error::AppError;
-use crate::models::ProjectDto;
+use crate::models::{ProjectDto, SortBy};
+use crate::utils::id::new_uuid;
+use crate::utils::pinyin::pinyin_keys;
 use chrono::Utc;
 use rusqlite::{params, Connection};
 
 /// 获取所有项目
-pub fn list_projects(conn: &Connection) -> Result<Vec<ProjectDto>, AppError> {
-    let mut stmt = conn.prepare(
-        "SELECT id, name, created_at, updated_at FROM projects ORDER BY created_at DESC",
-    )?;
+pub fn list_projects(conn: &Connection, sort_by: SortBy) -> Result<Vec<ProjectDto>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, name, created_at, updated_at, uuid FROM projects WHERE deleted_at IS NULL {}",
+        sort_by.order_clause()
+    ))?;
 
     let projects = stmt
         .query_map([], |row| {
             Ok(ProjectDto {
                 id: row.get(0)?,
+                uuid: row.get(4)?,
                 name: row.get(1)?,
                 created_at: row.get(2)?,
                 updated_at: row.get(3)?,
@@ -30,16 +34,19 @@ pub fn create_project(conn: &Connection, name: &str) -> Result<ProjectDto, AppEr
     }
 
     let now = Utc::now().to_rfc3339();
+    let keys = pinyin_keys(name);
+    let uuid = new_uuid();
 
     conn.execute(
-        "INSERT INTO projects (name, created_at) VALUES (?1, ?2)",
-        params![name, now],
+        "INSERT INTO projects (uuid, name, name_pinyin, name_initials, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![uuid, name, keys.full, keys.initials, now],
     )?;
 
     let id = conn.last_insert_rowid();
 
     Ok(ProjectDto {
         id,
+        uuid,
         name: name.to_string(),
         created_at: now,
         updated_at: None,
@@ -53,10 +60,12 @@ pub fn update_project(conn: &Connection, id: i64, name: &str) -> Result<(), AppE
     }
 
     let now = Utc::now().to_rfc3339();
+    let keys = pinyin_keys(name);
 
     let affected = conn.execute(
-        "UPDATE projects SET name = ?1, updated_at = ?2 WHERE id = ?3",
-        params![name, now, id],
+        "UPDATE projects SET name = ?1, name_pinyin = ?2, name_initials = ?3, updated_at = ?4 
+         WHERE id = ?5 AND deleted_at IS NULL",
+        params![name, keys.full, keys.initials, now, id],
     )?;
 
     if affected == 0 {
@@ -66,9 +75,13 @@ pub fn update_project(conn: &Connection, id: i64, name: &str) -> Result<(), AppE
     Ok(())
 }
 
-/// 删除项目
+/// 删除项目（移入回收站，项目下的任务和提示词随之隐藏）
 pub fn delete_project(conn: &Connection, id: i64) -> Result<(), AppError> {
-    let affected = conn.execute("DELETE FROM projects WHERE id = ?1", params![id])?;
+    let now = Utc::now().to_rfc3339();
+    let affected = conn.execute(
+        "UPDATE projects SET deleted_at = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+        params![now, id],
+    )?;
 
     if affected == 0 {
         return Err(AppError::NotFound(format!("项目 {} 不存在", id)));
@@ -80,11 +93,12 @@ pub fn delete_project(conn: &Connection, id: i64) -> Result<(), AppError> {
 /// 获取单个项目
 pub fn get_project(conn: &Connection, id: i64) -> Result<ProjectDto, AppError> {
     let project = conn.query_row(
-        "SELECT id, name, created_at, updated_at FROM projects WHERE id = ?1",
+        "SELECT id, name, created_at, updated_at, uuid FROM projects WHERE id = ?1 AND deleted_at IS NULL",
         params![id],
         |row| {
             Ok(ProjectDto {
                 id: row.get(0)?,
+                uuid: row.get(4)?,
                 name: row.get(1)?,
                 created_at: row.get(2)?,
                 updated_at: row.get(3)?,
@@ -94,3 +108,16 @@ pub fn get_project(conn: &Connection, id: i64) -> Result<ProjectDto, AppError> {
 
     Ok(project)
 }
+
+/// 按全局唯一 ID 获取项目
+pub fn get_project_by_uuid(conn: &Connection, uuid: &str) -> Result<ProjectDto, AppError> {
+    let id: i64 = conn
+        .query_row(
+            "SELECT id FROM projects WHERE uuid = ?1 AND deleted_at IS NULL",
+            params![uuid],
+            |row| row.get(0),
+        )
+        .map_err(|_| AppError::NotFound(format!("项目 {} 不存在", uuid)))?;
+
+    get_project(conn, id)
+}
@@ -1,5 +1,9 @@
 use crate::error::AppError;
-use crate::models::PromptEntryDto;
+use crate::models::{PromptEntryDto, TagMatchMode};
+use crate::repositories::revision_repository;
+use crate::repositories::tag_repository::{self, TAGS_JSON_COLUMN};
+use crate::utils::id::new_uuid;
+use crate::utils::pinyin::pinyin_keys;
 use chrono::Utc;
 use rusqlite::{params, Connection};
 
@@ -10,10 +14,12 @@ pub fn list_prompts_by_task(
     start_time: Option<&str>,
     end_time: Option<&str>,
     tags: Option<&[String]>,
+    tag_mode: TagMatchMode,
 ) -> Result<Vec<PromptEntryDto>, AppError> {
-    let mut sql = String::from(
-        "SELECT id, task_id, title, content, tags, model, created_at, updated_at 
-         FROM prompt_entries WHERE task_id = ?",
+    let mut sql = format!(
+        "SELECT id, task_id, title, content, {}, model, created_at, updated_at, uuid 
+         FROM prompt_entries WHERE task_id = ? AND deleted_at IS NULL",
+        TAGS_JSON_COLUMN
     );
 
     let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(task_id)];
@@ -26,6 +32,27 @@ pub fn list_prompts_by_task(
         sql.push_str(" AND created_at <= ?");
         params_vec.push(Box::new(end.to_string()));
     }
+    if let Some(filter_tags) = tags.filter(|t| !t.is_empty()) {
+        let placeholders = vec!["?"; filter_tags.len()].join(", ");
+        sql.push_str(&format!(
+            " AND id IN (
+                SELECT pt.prompt_id FROM prompt_tags pt
+                JOIN tags tg ON tg.id = pt.tag_id
+                WHERE tg.name IN ({})
+                GROUP BY pt.prompt_id",
+            placeholders
+        ));
+        for tag in filter_tags {
+            params_vec.push(Box::new(tag.clone()));
+        }
+        // 全部匹配：命中的不同标签数必须等于筛选标签数
+        if tag_mode == TagMatchMode::All {
+            let distinct: std::collections::HashSet<&String> = filter_tags.iter().collect();
+            sql.push_str(" HAVING COUNT(DISTINCT tg.id) = ?");
+            params_vec.push(Box::new(distinct.len() as i64));
+        }
+        sql.push(')');
+    }
 
     sql.push_str(" ORDER BY created_at DESC");
 
@@ -33,35 +60,21 @@ pub fn list_prompts_by_task(
     let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
 
     let prompts: Vec<PromptEntryDto> = stmt
-        .query_map(params_refs.as_slice(), |row| parse_prompt_row(row))?
+        .query_map(params_refs.as_slice(), parse_prompt_row)?
         .collect::<Result<Vec<_>, _>>()?;
 
-    // 如果有标签过滤，在内存中过滤
-    let prompts = if let Some(filter_tags) = tags {
-        prompts
-            .into_iter()
-            .filter(|p| {
-                if let Some(prompt_tags) = &p.tags {
-                    filter_tags.iter().any(|t| prompt_tags.contains(t))
-                } else {
-                    false
-                }
-            })
-            .collect()
-    } else {
-        prompts
-    };
-
     Ok(prompts)
 }
 
 fn parse_prompt_row(row: &rusqlite::Row) -> rusqlite::Result<PromptEntryDto> {
     let tags_json: Option<String> = row.get(4)?;
     let tags: Option<Vec<String>> = tags_json
-        .and_then(|s| serde_json::from_str(&s).ok());
+        .and_then(|s| serde_json::from_str::<Vec<String>>(&s).ok())
+        .filter(|t| !t.is_empty());
 
     Ok(PromptEntryDto {
         id: row.get(0)?,
+        uuid: row.get(8)?,
         task_id: row.get(1)?,
         title: row.get(2)?,
         content: row.get(3)?,
@@ -87,18 +100,28 @@ pub fn create_prompt(
     }
 
     let now = Utc::now().to_rfc3339();
-    let tags_json = tags.map(|t| serde_json::to_string(t).unwrap_or_default());
+    let keys = pinyin_keys(title.unwrap_or(""));
+    let uuid = new_uuid();
+
+    // 记录和标签一起写入；调用方已开启事务（如导入）时直接沿用
+    let tx = conn.is_autocommit().then(|| conn.unchecked_transaction()).transpose()?;
 
     conn.execute(
-        "INSERT INTO prompt_entries (task_id, title, content, tags, model, created_at) 
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-        params![task_id, title, content, tags_json, model, now],
+        "INSERT INTO prompt_entries (uuid, task_id, title, title_pinyin, title_initials, content, model, created_at) 
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![uuid, task_id, title, keys.full, keys.initials, content, model, now],
     )?;
 
     let id = conn.last_insert_rowid();
+    tag_repository::set_prompt_tags(conn, id, tags.unwrap_or(&[]))?;
+
+    if let Some(tx) = tx {
+        tx.commit()?;
+    }
 
     Ok(PromptEntryDto {
         id,
+        uuid,
         task_id,
         title: title.map(|s| s.to_string()),
         content: content.to_string(),
@@ -123,29 +146,83 @@ pub fn update_prompt(
 
     let new_title = title.or(current.title.as_deref());
     let new_content = content.unwrap_or(&current.content);
-    let new_tags = tags.map(|t| t.to_vec()).or(current.tags);
+    let new_tags = tags.map(|t| t.to_vec()).or_else(|| current.tags.clone());
     let new_model = model.or(current.model.as_deref());
 
+    write_prompt(conn, &current, new_title, new_content, new_tags.as_deref(), new_model)
+}
+
+/// 将提示词写为新的状态，写入前把当前状态存为一条修订记录
+///
+/// 与 `update_prompt` 不同，这里的每个字段都按原样写入（`None` 会清空该字段），
+/// 供恢复修订等需要完整覆盖的场景使用。
+pub fn write_prompt(
+    conn: &Connection,
+    current: &PromptEntryDto,
+    title: Option<&str>,
+    content: &str,
+    tags: Option<&[String]>,
+    model: Option<&str>,
+) -> Result<(), AppError> {
     // 允许空格内容作为占位符
-    if new_content.is_empty() {
+    if content.is_empty() {
         return Err(AppError::ValidationError("提示词内容不能为空".to_string()));
     }
 
+    // 修订、内容和标签一起写入；调用方已开启事务（如导入）时直接沿用
+    let tx = conn.is_autocommit().then(|| conn.unchecked_transaction()).transpose()?;
+
+    let unchanged = current.title.as_deref() == title
+        && current.content == content
+        && current.tags.as_deref().unwrap_or(&[]) == tags.unwrap_or(&[])
+        && current.model.as_deref() == model;
+    if !unchanged {
+        revision_repository::create_revision(conn, current)?;
+    }
+
     let now = Utc::now().to_rfc3339();
-    let tags_json = new_tags.as_ref().map(|t| serde_json::to_string(t).unwrap_or_default());
+    let keys = pinyin_keys(title.unwrap_or(""));
 
     conn.execute(
-        "UPDATE prompt_entries SET title = ?1, content = ?2, tags = ?3, model = ?4, updated_at = ?5 
-         WHERE id = ?6",
-        params![new_title, new_content, tags_json, new_model, now, id],
+        "UPDATE prompt_entries SET title = ?1, title_pinyin = ?2, title_initials = ?3, content = ?4, model = ?5, updated_at = ?6 
+         WHERE id = ?7",
+        params![title, keys.full, keys.initials, content, model, now, current.id],
     )?;
+    tag_repository::set_prompt_tags(conn, current.id, tags.unwrap_or(&[]))?;
+
+    if let Some(tx) = tx {
+        tx.commit()?;
+    }
 
     Ok(())
 }
 
-/// 删除提示词记录
+/// 将提示词恢复为指定修订的内容，返回恢复后的记录
+///
+/// 恢复本身也是一次更新，恢复前的内容会作为新的修订保存，因此可以撤销。
+pub fn restore_revision(conn: &Connection, revision_id: i64) -> Result<PromptEntryDto, AppError> {
+    let revision = revision_repository::get_revision(conn, revision_id)?;
+    let current = get_prompt(conn, revision.prompt_id)?;
+
+    write_prompt(
+        conn,
+        &current,
+        revision.title.as_deref(),
+        &revision.content,
+        revision.tags.as_deref(),
+        revision.model.as_deref(),
+    )?;
+
+    get_prompt(conn, revision.prompt_id)
+}
+
+/// 删除提示词记录（移入回收站）
 pub fn delete_prompt(conn: &Connection, id: i64) -> Result<(), AppError> {
-    let affected = conn.execute("DELETE FROM prompt_entries WHERE id = ?1", params![id])?;
+    let now = Utc::now().to_rfc3339();
+    let affected = conn.execute(
+        "UPDATE prompt_entries SET deleted_at = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+        params![now, id],
+    )?;
 
     if affected == 0 {
         return Err(AppError::NotFound(format!("提示词记录 {} 不存在", id)));
@@ -157,11 +234,27 @@ pub fn delete_prompt(conn: &Connection, id: i64) -> Result<(), AppError> {
 /// 获取单个提示词记录
 pub fn get_prompt(conn: &Connection, id: i64) -> Result<PromptEntryDto, AppError> {
     let prompt = conn.query_row(
-        "SELECT id, task_id, title, content, tags, model, created_at, updated_at 
-         FROM prompt_entries WHERE id = ?1",
+        &format!(
+            "SELECT id, task_id, title, content, {}, model, created_at, updated_at, uuid 
+             FROM prompt_entries WHERE id = ?1 AND deleted_at IS NULL",
+            TAGS_JSON_COLUMN
+        ),
         params![id],
-        |row| parse_prompt_row(row),
+        parse_prompt_row,
     ).map_err(|_| AppError::NotFound(format!("提示词记录 {} 不存在", id)))?;
 
     Ok(prompt)
 }
+
+/// 按全局唯一 ID 获取提示词记录
+pub fn get_prompt_by_uuid(conn: &Connection, uuid: &str) -> Result<PromptEntryDto, AppError> {
+    let id: i64 = conn
+        .query_row(
+            "SELECT id FROM prompt_entries WHERE uuid = ?1 AND deleted_at IS NULL",
+            params![uuid],
+            |row| row.get(0),
+        )
+        .map_err(|_| AppError::NotFound(format!("提示词记录 {} 不存在", uuid)))?;
+
+    get_prompt(conn, id)
+}
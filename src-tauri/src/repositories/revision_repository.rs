@@ -0,0 +1,124 @@
+use crate::error::AppError;
+use crate::models::{PromptEntryDto, PromptRevisionDto};
+use chrono::Utc;
+use rusqlite::{params, Connection};
+
+/// 将提示词的当前状态保存为一条修订记录
+pub fn create_revision(conn: &Connection, prompt: &PromptEntryDto) -> Result<i64, AppError> {
+    let now = Utc::now().to_rfc3339();
+    let tags_json = prompt
+        .tags
+        .as_ref()
+        .map(|t| serde_json::to_string(t).unwrap_or_default());
+    let version_at = prompt.updated_at.as_deref().unwrap_or(&prompt.created_at);
+
+    conn.execute(
+        "INSERT INTO prompt_revisions (prompt_id, title, content, tags, model, version_at, created_at) 
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            prompt.id,
+            prompt.title,
+            prompt.content,
+            tags_json,
+            prompt.model,
+            version_at,
+            now
+        ],
+    )?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+fn parse_revision_row(row: &rusqlite::Row) -> rusqlite::Result<PromptRevisionDto> {
+    let tags_json: Option<String> = row.get(4)?;
+    let tags: Option<Vec<String>> = tags_json.and_then(|s| serde_json::from_str(&s).ok());
+
+    Ok(PromptRevisionDto {
+        id: row.get(0)?,
+        prompt_id: row.get(1)?,
+        title: row.get(2)?,
+        content: row.get(3)?,
+        tags,
+        model: row.get(5)?,
+        version_at: row.get(6)?,
+        created_at: row.get(7)?,
+    })
+}
+
+/// 获取提示词的所有修订记录（最新的在前）
+pub fn list_revisions(conn: &Connection, prompt_id: i64) -> Result<Vec<PromptRevisionDto>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, prompt_id, title, content, tags, model, version_at, created_at 
+         FROM prompt_revisions WHERE prompt_id = ?1 ORDER BY id DESC",
+    )?;
+
+    let revisions = stmt
+        .query_map(params![prompt_id], parse_revision_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(revisions)
+}
+
+/// 获取单条修订记录
+pub fn get_revision(conn: &Connection, id: i64) -> Result<PromptRevisionDto, AppError> {
+    let revision = conn
+        .query_row(
+            "SELECT id, prompt_id, title, content, tags, model, version_at, created_at 
+             FROM prompt_revisions WHERE id = ?1",
+            params![id],
+            parse_revision_row,
+        )
+        .map_err(|_| AppError::NotFound(format!("修订记录 {} 不存在", id)))?;
+
+    Ok(revision)
+}
+
+/// 清理修订记录，返回删除的条数
+///
+/// - `prompt_id`: 只清理指定提示词的修订；为空时作用于全部提示词
+/// - `keep_latest`: 每个提示词保留最新的 N 条修订
+/// - `before`: 删除早于该时间（RFC 3339）生成的修订
+///
+/// 两个条件同时给出时，只删除同时满足两者的修订。
+pub fn prune_revisions(
+    conn: &Connection,
+    prompt_id: Option<i64>,
+    keep_latest: Option<i64>,
+    before: Option<&str>,
+) -> Result<usize, AppError> {
+    if keep_latest.is_none() && before.is_none() {
+        return Err(AppError::ValidationError(
+            "请指定要保留的修订数量或清理的截止时间".to_string(),
+        ));
+    }
+    if matches!(keep_latest, Some(n) if n < 0) {
+        return Err(AppError::ValidationError("保留数量不能为负数".to_string()));
+    }
+
+    let mut sql = String::from("DELETE FROM prompt_revisions WHERE 1 = 1");
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+    if let Some(pid) = prompt_id {
+        sql.push_str(" AND prompt_id = ?");
+        params_vec.push(Box::new(pid));
+    }
+    if let Some(before) = before {
+        sql.push_str(" AND created_at < ?");
+        params_vec.push(Box::new(before.to_string()));
+    }
+    if let Some(keep) = keep_latest {
+        sql.push_str(
+            " AND id NOT IN (
+                SELECT r.id FROM prompt_revisions r
+                WHERE r.prompt_id = prompt_revisions.prompt_id
+                ORDER BY r.id DESC LIMIT ?
+            )",
+        );
+        params_vec.push(Box::new(keep));
+    }
+
+    let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
+    let deleted = conn.execute(&sql, params_refs.as_slice())?;
+
+    Ok(deleted)
+}
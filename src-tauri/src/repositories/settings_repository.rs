@@ -0,0 +1,30 @@
+use crate::error::AppError;
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// 读取设置项，不存在时返回 None
+pub fn get_setting(conn: &Connection, key: &str) -> Result<Option<String>, AppError> {
+    let value = conn
+        .query_row(
+            "SELECT value FROM settings WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    Ok(value)
+}
+
+/// 写入设置项（存在则覆盖）
+pub fn set_setting(conn: &Connection, key: &str, value: &str) -> Result<(), AppError> {
+    let now = Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO settings (key, value, updated_at) VALUES (?1, ?2, ?3)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        params![key, value, now],
+    )?;
+
+    Ok(())
+}
+
@@ -0,0 +1,150 @@
+use crate::error::AppError;
+use crate::models::SyncConflictDto;
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// 单个提示词文件的同步状态
+#[derive(Debug, Clone)]
+pub struct SyncRecord {
+    pub prompt_id: i64,
+    /// 相对于同步文件夹的路径，使用 `/` 分隔
+    pub path: String,
+    /// 上次同步时文件内容的 SHA-256
+    pub content_hash: String,
+    /// 上次同步时提示词的版本（updated_at，未更新过时为 created_at）
+    pub db_version: String,
+    pub conflict: Option<String>,
+}
+
+const RECORD_COLUMNS: &str = "prompt_id, path, content_hash, db_version, conflict";
+
+fn parse_record_row(row: &rusqlite::Row) -> rusqlite::Result<SyncRecord> {
+    Ok(SyncRecord {
+        prompt_id: row.get(0)?,
+        path: row.get(1)?,
+        content_hash: row.get(2)?,
+        db_version: row.get(3)?,
+        conflict: row.get(4)?,
+    })
+}
+
+/// 按提示词获取同步状态
+pub fn get_record(conn: &Connection, prompt_id: i64) -> Result<Option<SyncRecord>, AppError> {
+    conn.query_row(
+        &format!("SELECT {} FROM sync_state WHERE prompt_id = ?1", RECORD_COLUMNS),
+        params![prompt_id],
+        parse_record_row,
+    )
+    .optional()
+    .map_err(Into::into)
+}
+
+/// 按文件路径获取同步状态
+pub fn get_record_by_path(conn: &Connection, path: &str) -> Result<Option<SyncRecord>, AppError> {
+    conn.query_row(
+        &format!("SELECT {} FROM sync_state WHERE path = ?1", RECORD_COLUMNS),
+        params![path],
+        parse_record_row,
+    )
+    .optional()
+    .map_err(Into::into)
+}
+
+/// 按提示词的全局唯一 ID 获取同步状态
+pub fn get_record_by_uuid(conn: &Connection, uuid: &str) -> Result<Option<SyncRecord>, AppError> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM sync_state WHERE prompt_id = (SELECT id FROM prompt_entries WHERE uuid = ?1)",
+            RECORD_COLUMNS
+        ),
+        params![uuid],
+        parse_record_row,
+    )
+    .optional()
+    .map_err(Into::into)
+}
+
+/// 获取全部同步状态
+pub fn list_records(conn: &Connection) -> Result<Vec<SyncRecord>, AppError> {
+    let mut stmt = conn.prepare(&format!("SELECT {} FROM sync_state", RECORD_COLUMNS))?;
+    let records = stmt
+        .query_map([], parse_record_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(records)
+}
+
+/// 记录一次成功的同步，并清除冲突标记
+pub fn save_record(
+    conn: &Connection,
+    prompt_id: i64,
+    path: &str,
+    content_hash: &str,
+    db_version: &str,
+) -> Result<(), AppError> {
+    let now = Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO sync_state (prompt_id, path, content_hash, db_version, synced_at) VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(prompt_id) DO UPDATE SET path = excluded.path, content_hash = excluded.content_hash,
+             db_version = excluded.db_version, conflict = NULL, conflict_at = NULL, synced_at = excluded.synced_at",
+        params![prompt_id, path, content_hash, db_version, now],
+    )?;
+
+    Ok(())
+}
+
+/// 标记冲突；没有同步记录时以当前文件状态新建一条
+pub fn mark_conflict(
+    conn: &Connection,
+    prompt_id: i64,
+    path: &str,
+    content_hash: &str,
+    reason: &str,
+) -> Result<(), AppError> {
+    let now = Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO sync_state (prompt_id, path, content_hash, db_version, conflict, conflict_at, synced_at)
+         VALUES (?1, ?2, ?3, '', ?4, ?5, ?5)
+         ON CONFLICT(prompt_id) DO UPDATE SET conflict = excluded.conflict, conflict_at = excluded.conflict_at",
+        params![prompt_id, path, content_hash, reason, now],
+    )?;
+
+    Ok(())
+}
+
+/// 删除同步状态
+pub fn delete_record(conn: &Connection, prompt_id: i64) -> Result<(), AppError> {
+    conn.execute("DELETE FROM sync_state WHERE prompt_id = ?1", params![prompt_id])?;
+    Ok(())
+}
+
+/// 清空全部同步状态（更换同步文件夹时）
+pub fn clear_records(conn: &Connection) -> Result<(), AppError> {
+    conn.execute("DELETE FROM sync_state", [])?;
+    Ok(())
+}
+
+/// 获取所有未解决的冲突
+pub fn list_conflicts(conn: &Connection) -> Result<Vec<SyncConflictDto>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT s.prompt_id, pe.title, s.path, s.conflict, s.conflict_at FROM sync_state s
+         JOIN prompt_entries pe ON pe.id = s.prompt_id
+         WHERE s.conflict IS NOT NULL ORDER BY s.conflict_at DESC",
+    )?;
+
+    let conflicts = stmt
+        .query_map([], |row| {
+            Ok(SyncConflictDto {
+                prompt_id: row.get(0)?,
+                title: row.get(1)?,
+                path: row.get(2)?,
+                reason: row.get(3)?,
+                detected_at: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(conflicts)
+}
@@ -0,0 +1,144 @@
+use crate::error::AppError;
+use crate::models::TagDto;
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// 按标签顺序聚合为 JSON 数组的 SQL 列表达式，需在查询 `prompt_entries`（不带别名）时使用
+pub const TAGS_JSON_COLUMN: &str = "(SELECT json_group_array(tg.name ORDER BY pt.position) 
+     FROM prompt_tags pt JOIN tags tg ON tg.id = pt.tag_id 
+     WHERE pt.prompt_id = prompt_entries.id)";
+
+/// 获取所有标签及使用次数（按使用次数降序）
+pub fn list_tags(conn: &Connection) -> Result<Vec<TagDto>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT tg.id, tg.name, 
+                (SELECT COUNT(*) FROM prompt_tags pt
+                 JOIN prompt_entries pe ON pe.id = pt.prompt_id
+                 JOIN tasks t ON pe.task_id = t.id
+                 JOIN projects p ON t.project_id = p.id
+                 WHERE pt.tag_id = tg.id
+                   AND pe.deleted_at IS NULL AND t.deleted_at IS NULL AND p.deleted_at IS NULL) AS usage_count
+         FROM tags tg
+         ORDER BY usage_count DESC, tg.name",
+    )?;
+
+    let tags = stmt
+        .query_map([], |row| {
+            Ok(TagDto {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                usage_count: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(tags)
+}
+
+/// 按名称查找标签 ID
+fn find_tag_id(conn: &Connection, name: &str) -> Result<Option<i64>, AppError> {
+    let id = conn
+        .query_row("SELECT id FROM tags WHERE name = ?1", params![name], |row| row.get(0))
+        .optional()?;
+
+    Ok(id)
+}
+
+/// 获取标签 ID，不存在时创建
+fn get_or_create_tag(conn: &Connection, name: &str) -> Result<i64, AppError> {
+    if let Some(id) = find_tag_id(conn, name)? {
+        return Ok(id);
+    }
+
+    let now = Utc::now().to_rfc3339();
+    conn.execute(
+        "INSERT INTO tags (name, created_at) VALUES (?1, ?2)",
+        params![name, now],
+    )?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+/// 设置提示词的标签（整体替换，保留传入顺序，忽略空白和重复标签）
+pub fn set_prompt_tags(conn: &Connection, prompt_id: i64, tags: &[String]) -> Result<(), AppError> {
+    conn.execute("DELETE FROM prompt_tags WHERE prompt_id = ?1", params![prompt_id])?;
+
+    let mut position = 0;
+    for name in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        let tag_id = get_or_create_tag(conn, name)?;
+        let inserted = conn.execute(
+            "INSERT OR IGNORE INTO prompt_tags (prompt_id, tag_id, position) VALUES (?1, ?2, ?3)",
+            params![prompt_id, tag_id, position],
+        )?;
+        position += inserted as i64;
+    }
+
+    Ok(())
+}
+
+/// 重命名标签，所有使用该标签的提示词随之更新
+pub fn rename_tag(conn: &Connection, id: i64, new_name: &str) -> Result<(), AppError> {
+    let new_name = new_name.trim();
+    if new_name.is_empty() {
+        return Err(AppError::ValidationError("标签名称不能为空".to_string()));
+    }
+
+    if let Some(existing) = find_tag_id(conn, new_name)? {
+        if existing != id {
+            return Err(AppError::ValidationError(format!(
+                "标签 \"{}\" 已存在，请使用合并标签",
+                new_name
+            )));
+        }
+    }
+
+    let affected = conn.execute("UPDATE tags SET name = ?1 WHERE id = ?2", params![new_name, id])?;
+
+    if affected == 0 {
+        return Err(AppError::NotFound(format!("标签 {} 不存在", id)));
+    }
+
+    Ok(())
+}
+
+/// 将源标签合并到目标标签：源标签的所有使用改为目标标签，然后删除源标签
+pub fn merge_tags(conn: &Connection, source_id: i64, target_id: i64) -> Result<(), AppError> {
+    if source_id == target_id {
+        return Err(AppError::ValidationError("不能将标签合并到自身".to_string()));
+    }
+    for id in [source_id, target_id] {
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM tags WHERE id = ?1)",
+            params![id],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(AppError::NotFound(format!("标签 {} 不存在", id)));
+        }
+    }
+
+    let tx = conn.unchecked_transaction()?;
+
+    // 已同时带有两个标签的提示词会被忽略，随后由删除源标签时的级联清理
+    tx.execute(
+        "INSERT OR IGNORE INTO prompt_tags (prompt_id, tag_id, position)
+         SELECT prompt_id, ?2, position FROM prompt_tags WHERE tag_id = ?1",
+        params![source_id, target_id],
+    )?;
+    tx.execute("DELETE FROM tags WHERE id = ?1", params![source_id])?;
+
+    tx.commit()?;
+
+    Ok(())
+}
+
+/// 删除标签，并从所有提示词上移除
+pub fn delete_tag(conn: &Connection, id: i64) -> Result<(), AppError> {
+    let affected = conn.execute("DELETE FROM tags WHERE id = ?1", params![id])?;
+
+    if affected == 0 {
+        return Err(AppError::NotFound(format!("标签 {} 不存在", id)));
+    }
+
+    Ok(())
+}
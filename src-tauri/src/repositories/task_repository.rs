@@ -1,19 +1,27 @@
 use crate::error::AppError;
-use crate::models::TaskDto;
+use crate::models::{SortBy, TaskDto};
+use crate::utils::id::new_uuid;
+use crate::utils::pinyin::pinyin_keys;
 use chrono::Utc;
 use rusqlite::{params, Connection};
 
 /// 获取项目下的所有任务
-pub fn list_tasks_by_project(conn: &Connection, project_id: i64) -> Result<Vec<TaskDto>, AppError> {
-    let mut stmt = conn.prepare(
-        "SELECT id, project_id, name, description, created_at, updated_at 
-         FROM tasks WHERE project_id = ?1 ORDER BY created_at DESC",
-    )?;
+pub fn list_tasks_by_project(
+    conn: &Connection,
+    project_id: i64,
+    sort_by: SortBy,
+) -> Result<Vec<TaskDto>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, project_id, name, description, created_at, updated_at, uuid 
+         FROM tasks WHERE project_id = ?1 AND deleted_at IS NULL {}",
+        sort_by.order_clause()
+    ))?;
 
     let tasks = stmt
         .query_map(params![project_id], |row| {
             Ok(TaskDto {
                 id: row.get(0)?,
+                uuid: row.get(6)?,
                 project_id: row.get(1)?,
                 name: row.get(2)?,
                 description: row.get(3)?,
@@ -38,16 +46,20 @@ pub fn create_task(
     }
 
     let now = Utc::now().to_rfc3339();
+    let keys = pinyin_keys(name);
+    let uuid = new_uuid();
 
     conn.execute(
-        "INSERT INTO tasks (project_id, name, description, created_at) VALUES (?1, ?2, ?3, ?4)",
-        params![project_id, name, description, now],
+        "INSERT INTO tasks (uuid, project_id, name, name_pinyin, name_initials, description, created_at) 
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![uuid, project_id, name, keys.full, keys.initials, description, now],
     )?;
 
     let id = conn.last_insert_rowid();
 
     Ok(TaskDto {
         id,
+        uuid,
         project_id,
         name: name.to_string(),
         description: description.map(|s| s.to_string()),
@@ -65,11 +77,13 @@ pub fn update_task(
 ) -> Result<(), AppError> {
     // 获取当前任务
     let current: TaskDto = conn.query_row(
-        "SELECT id, project_id, name, description, created_at, updated_at FROM tasks WHERE id = ?1",
+        "SELECT id, project_id, name, description, created_at, updated_at, uuid FROM tasks 
+         WHERE id = ?1 AND deleted_at IS NULL",
         params![id],
         |row| {
             Ok(TaskDto {
                 id: row.get(0)?,
+                uuid: row.get(6)?,
                 project_id: row.get(1)?,
                 name: row.get(2)?,
                 description: row.get(3)?,
@@ -87,18 +101,24 @@ pub fn update_task(
     }
 
     let now = Utc::now().to_rfc3339();
+    let keys = pinyin_keys(new_name);
 
     conn.execute(
-        "UPDATE tasks SET name = ?1, description = ?2, updated_at = ?3 WHERE id = ?4",
-        params![new_name, new_description, now, id],
+        "UPDATE tasks SET name = ?1, name_pinyin = ?2, name_initials = ?3, description = ?4, updated_at = ?5 
+         WHERE id = ?6",
+        params![new_name, keys.full, keys.initials, new_description, now, id],
     )?;
 
     Ok(())
 }
 
-/// 删除任务
+/// 删除任务（移入回收站，任务下的提示词随之隐藏）
 pub fn delete_task(conn: &Connection, id: i64) -> Result<(), AppError> {
-    let affected = conn.execute("DELETE FROM tasks WHERE id = ?1", params![id])?;
+    let now = Utc::now().to_rfc3339();
+    let affected = conn.execute(
+        "UPDATE tasks SET deleted_at = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+        params![now, id],
+    )?;
 
     if affected == 0 {
         return Err(AppError::NotFound(format!("任务 {} 不存在", id)));
@@ -110,11 +130,13 @@ pub fn delete_task(conn: &Connection, id: i64) -> Result<(), AppError> {
 /// 获取单个任务
 pub fn get_task(conn: &Connection, id: i64) -> Result<TaskDto, AppError> {
     let task = conn.query_row(
-        "SELECT id, project_id, name, description, created_at, updated_at FROM tasks WHERE id = ?1",
+        "SELECT id, project_id, name, description, created_at, updated_at, uuid FROM tasks 
+         WHERE id = ?1 AND deleted_at IS NULL",
         params![id],
         |row| {
             Ok(TaskDto {
                 id: row.get(0)?,
+                uuid: row.get(6)?,
                 project_id: row.get(1)?,
                 name: row.get(2)?,
                 description: row.get(3)?,
@@ -126,3 +148,16 @@ pub fn get_task(conn: &Connection, id: i64) -> Result<TaskDto, AppError> {
 
     Ok(task)
 }
+
+/// 按全局唯一 ID 获取任务
+pub fn get_task_by_uuid(conn: &Connection, uuid: &str) -> Result<TaskDto, AppError> {
+    let id: i64 = conn
+        .query_row(
+            "SELECT id FROM tasks WHERE uuid = ?1 AND deleted_at IS NULL",
+            params![uuid],
+            |row| row.get(0),
+        )
+        .map_err(|_| AppError::NotFound(format!("任务 {} 不存在", uuid)))?;
+
+    get_task(conn, id)
+}
@@ -0,0 +1,181 @@
+use crate::error::AppError;
+use crate::models::{TrashItemDto, TrashKind};
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// 提示词无标题时，回收站中显示的内容预览长度（字符数）
+const PREVIEW_CHARS: usize = 50;
+
+/// 获取回收站中的所有条目（最近删除的在前）
+///
+/// 只列出被直接删除的条目；随项目或任务一起隐藏的下级条目不单独列出，
+/// 恢复上级条目即可一并恢复。
+pub fn list_trash(conn: &Connection) -> Result<Vec<TrashItemDto>, AppError> {
+    let mut items = Vec::new();
+
+    let mut stmt = conn.prepare(
+        "SELECT id, name, deleted_at FROM projects WHERE deleted_at IS NOT NULL",
+    )?;
+    let projects = stmt.query_map([], |row| {
+        Ok(TrashItemDto {
+            kind: TrashKind::Project,
+            id: row.get(0)?,
+            name: row.get(1)?,
+            context: None,
+            deleted_at: row.get(2)?,
+        })
+    })?;
+    for item in projects {
+        items.push(item?);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT t.id, t.name, t.deleted_at, p.name FROM tasks t
+         JOIN projects p ON t.project_id = p.id
+         WHERE t.deleted_at IS NOT NULL",
+    )?;
+    let tasks = stmt.query_map([], |row| {
+        Ok(TrashItemDto {
+            kind: TrashKind::Task,
+            id: row.get(0)?,
+            name: row.get(1)?,
+            context: Some(row.get(3)?),
+            deleted_at: row.get(2)?,
+        })
+    })?;
+    for item in tasks {
+        items.push(item?);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT pe.id, pe.title, pe.content, pe.deleted_at, p.name, t.name FROM prompt_entries pe
+         JOIN tasks t ON pe.task_id = t.id
+         JOIN projects p ON t.project_id = p.id
+         WHERE pe.deleted_at IS NOT NULL",
+    )?;
+    let prompts = stmt.query_map([], |row| {
+        let title: Option<String> = row.get(1)?;
+        let content: String = row.get(2)?;
+        let project_name: String = row.get(4)?;
+        let task_name: String = row.get(5)?;
+
+        Ok(TrashItemDto {
+            kind: TrashKind::Prompt,
+            id: row.get(0)?,
+            name: title
+                .filter(|t| !t.trim().is_empty())
+                .unwrap_or_else(|| content.chars().take(PREVIEW_CHARS).collect()),
+            context: Some(format!("{} / {}", project_name, task_name)),
+            deleted_at: row.get(3)?,
+        })
+    })?;
+    for item in prompts {
+        items.push(item?);
+    }
+
+    items.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+
+    Ok(items)
+}
+
+/// 从回收站恢复条目
+///
+/// 上级项目或任务仍在回收站中时不能单独恢复，需先恢复上级。
+pub fn restore_item(conn: &Connection, kind: TrashKind, id: i64) -> Result<(), AppError> {
+    match kind {
+        TrashKind::Project => {}
+        TrashKind::Task => {
+            let project_trashed: Option<bool> = conn
+                .query_row(
+                    "SELECT p.deleted_at IS NOT NULL FROM tasks t
+                     JOIN projects p ON t.project_id = p.id WHERE t.id = ?1",
+                    params![id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if project_trashed == Some(true) {
+                return Err(AppError::ValidationError(
+                    "所属项目在回收站中，请先恢复项目".to_string(),
+                ));
+            }
+        }
+        TrashKind::Prompt => {
+            let parent_trashed: Option<bool> = conn
+                .query_row(
+                    "SELECT t.deleted_at IS NOT NULL OR p.deleted_at IS NOT NULL FROM prompt_entries pe
+                     JOIN tasks t ON pe.task_id = t.id
+                     JOIN projects p ON t.project_id = p.id WHERE pe.id = ?1",
+                    params![id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if parent_trashed == Some(true) {
+                return Err(AppError::ValidationError(
+                    "所属任务或项目在回收站中，请先恢复上级".to_string(),
+                ));
+            }
+        }
+    }
+
+    let affected = conn.execute(
+        &format!(
+            "UPDATE {} SET deleted_at = NULL WHERE id = ?1 AND deleted_at IS NOT NULL",
+            table_name(kind)
+        ),
+        params![id],
+    )?;
+
+    if affected == 0 {
+        return Err(AppError::NotFound(format!("回收站中不存在该条目: {}", id)));
+    }
+
+    Ok(())
+}
+
+/// 彻底删除回收站中的单个条目（下级数据随外键级联删除）
+pub fn purge_item(conn: &Connection, kind: TrashKind, id: i64) -> Result<(), AppError> {
+    let affected = conn.execute(
+        &format!(
+            "DELETE FROM {} WHERE id = ?1 AND deleted_at IS NOT NULL",
+            table_name(kind)
+        ),
+        params![id],
+    )?;
+
+    if affected == 0 {
+        return Err(AppError::NotFound(format!("回收站中不存在该条目: {}", id)));
+    }
+
+    Ok(())
+}
+
+/// 彻底删除回收站中删除时间早于 `cutoff`（RFC 3339）的条目；`cutoff` 为空时清空回收站
+///
+/// 返回被删除的回收站条目数（不含级联删除的下级数据）。
+pub fn purge(conn: &Connection, cutoff: Option<&str>) -> Result<usize, AppError> {
+    let mut purged = 0;
+
+    // 先删提示词、再删任务、最后删项目，使计数只包含回收站中直接列出的条目
+    for kind in [TrashKind::Prompt, TrashKind::Task, TrashKind::Project] {
+        let table = table_name(kind);
+        purged += match cutoff {
+            Some(cutoff) => conn.execute(
+                &format!("DELETE FROM {} WHERE deleted_at IS NOT NULL AND deleted_at < ?1", table),
+                params![cutoff],
+            )?,
+            None => conn.execute(
+                &format!("DELETE FROM {} WHERE deleted_at IS NOT NULL", table),
+                [],
+            )?,
+        };
+    }
+
+    Ok(purged)
+}
+
+fn table_name(kind: TrashKind) -> &'static str {
+    match kind {
+        TrashKind::Project => "projects",
+        TrashKind::Task => "tasks",
+        TrashKind::Prompt => "prompt_entries",
+    }
+}
@@ -0,0 +1,169 @@
+use crate::error::AppError;
+use crate::services::backup_service::{ExportData, EXPORT_VERSION};
+use crate::services::schema_service;
+use crate::utils::hash::sha256_hex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::{Cursor, Read, Write};
+use zip::result::ZipError;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// 当前压缩包格式版本
+pub const ARCHIVE_FORMAT: u32 = 1;
+
+const MANIFEST_NAME: &str = "manifest.json";
+const DATA_NAME: &str = "data.json";
+/// zip 本地文件头
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+/// 压缩包清单：记录版本、数量和每个成员的校验和，导入前据此校验
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub format: u32,
+    pub app_version: String,
+    /// 数据文件的导出格式版本
+    pub schema_version: i32,
+    pub exported_at: String,
+    pub counts: EntityCounts,
+    pub members: Vec<ArchiveMember>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntityCounts {
+    pub projects: usize,
+    pub tasks: usize,
+    pub prompts: usize,
+}
+
+impl From<&ExportData> for EntityCounts {
+    fn from(data: &ExportData) -> Self {
+        let tasks = data.projects.iter().flat_map(|p| &p.tasks);
+
+        EntityCounts {
+            projects: data.projects.len(),
+            tasks: tasks.clone().count(),
+            prompts: tasks.map(|t| t.prompts.len()).sum(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveMember {
+    pub name: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// 内容是否为 zip 压缩包（按文件头判断，不依赖扩展名）
+pub fn is_archive(bytes: &[u8]) -> bool {
+    bytes.starts_with(ZIP_MAGIC)
+}
+
+/// 生成压缩包：清单 + 数据文件
+pub fn archive_bytes(data: &ExportData) -> Result<Vec<u8>, AppError> {
+    let json = serde_json::to_vec_pretty(data)?;
+
+    let manifest = ArchiveManifest {
+        format: ARCHIVE_FORMAT,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        schema_version: data.version,
+        exported_at: data.exported_at.clone(),
+        counts: EntityCounts::from(data),
+        members: vec![ArchiveMember {
+            name: DATA_NAME.to_string(),
+            size: json.len() as u64,
+            sha256: sha256_hex(&json),
+        }],
+    };
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    zip.start_file(MANIFEST_NAME, options).map_err(zip_write_error)?;
+    zip.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+    zip.start_file(DATA_NAME, options).map_err(zip_write_error)?;
+    zip.write_all(&json)?;
+
+    Ok(zip.finish().map_err(zip_write_error)?.into_inner())
+}
+
+/// 读取并校验压缩包，返回其中的导出数据
+///
+/// 清单缺失、成员缺失或多出、大小或 SHA-256 不符、数量与清单不一致时均视为损坏，
+/// 此时不会返回任何数据，调用方也就不会改动数据库。
+pub fn read_archive(bytes: &[u8]) -> Result<ExportData, AppError> {
+    let mut zip = ZipArchive::new(Cursor::new(bytes)).map_err(zip_read_error)?;
+
+    let manifest: ArchiveManifest = serde_json::from_slice(&read_member(&mut zip, MANIFEST_NAME)?)
+        .map_err(|e| corrupted(format!("清单无法解析: {}", e)))?;
+
+    if manifest.format > ARCHIVE_FORMAT {
+        return Err(AppError::ValidationError(format!(
+            "备份格式版本 {} 高于当前支持的 {}，请升级应用后再导入",
+            manifest.format, ARCHIVE_FORMAT
+        )));
+    }
+    if manifest.schema_version > EXPORT_VERSION {
+        return Err(AppError::ValidationError(format!(
+            "数据格式版本 {} 高于当前支持的 {}，请升级应用后再导入",
+            manifest.schema_version, EXPORT_VERSION
+        )));
+    }
+
+    // 清单之外不应有其他成员
+    let listed: HashSet<&str> = manifest.members.iter().map(|m| m.name.as_str()).collect();
+    if let Some(extra) = zip.file_names().find(|n| *n != MANIFEST_NAME && !listed.contains(n)) {
+        return Err(corrupted(format!("包含清单中未列出的文件 {}", extra)));
+    }
+
+    let mut data_bytes = None;
+    for member in &manifest.members {
+        let bytes = read_member(&mut zip, &member.name)?;
+
+        if bytes.len() as u64 != member.size || sha256_hex(&bytes) != member.sha256 {
+            return Err(corrupted(format!("{} 校验和不匹配", member.name)));
+        }
+        if member.name == DATA_NAME {
+            data_bytes = Some(bytes);
+        }
+    }
+
+    let data_bytes = data_bytes.ok_or_else(|| corrupted(format!("缺少 {}", DATA_NAME)))?;
+    let data = schema_service::parse_export_data(&data_bytes)?;
+
+    if EntityCounts::from(&data) != manifest.counts {
+        return Err(corrupted("数据数量与清单不一致".to_string()));
+    }
+
+    Ok(data)
+}
+
+fn read_member(zip: &mut ZipArchive<Cursor<&[u8]>>, name: &str) -> Result<Vec<u8>, AppError> {
+    let mut file = zip.by_name(name).map_err(|e| match e {
+        ZipError::FileNotFound => corrupted(format!("缺少 {}", name)),
+        e => zip_read_error(e),
+    })?;
+
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)
+        .map_err(|e| corrupted(format!("{} 读取失败: {}", name, e)))?;
+
+    Ok(bytes)
+}
+
+fn corrupted(reason: String) -> AppError {
+    AppError::BackupCorrupted(reason)
+}
+
+/// 压缩包已在内存中，读取失败只可能是内容本身有问题
+fn zip_read_error(e: ZipError) -> AppError {
+    corrupted(e.to_string())
+}
+
+fn zip_write_error(e: ZipError) -> AppError {
+    match e {
+        ZipError::Io(e) => AppError::IoError(e),
+        e => AppError::Unknown(format!("写入压缩包失败: {}", e)),
+    }
+}
@@ -0,0 +1,279 @@
+use crate::db::DbState;
+use crate::error::AppError;
+use crate::models::{BackupFileDto, BackupSettings, ConflictPolicy, ImportMode, ImportReport};
+use crate::repositories::settings_repository;
+use crate::services::{backup_service, trash_service};
+use chrono::{Datelike, Local, NaiveDateTime, TimeZone};
+use log::{error, info};
+use rusqlite::Connection;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const ENABLED_KEY: &str = "backup_enabled";
+const INTERVAL_KEY: &str = "backup_interval_hours";
+const FOLDER_KEY: &str = "backup_folder";
+
+/// 默认每 24 小时备份一次
+const DEFAULT_INTERVAL_HOURS: u32 = 24;
+/// 后台检查是否需要备份、清理回收站的间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// 轮换策略：按天、周、月各保留最新的一份
+const KEEP_DAILY: usize = 7;
+const KEEP_WEEKLY: usize = 4;
+const KEEP_MONTHLY: usize = 12;
+
+const FILE_PREFIX: &str = "promptlog-";
+const FILE_SUFFIX: &str = ".promptlog.zip";
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// 获取自动备份设置
+pub fn get_settings(conn: &Connection) -> Result<BackupSettings, AppError> {
+    let enabled = settings_repository::get_setting(conn, ENABLED_KEY)?
+        .map(|v| v == "1")
+        .unwrap_or(true);
+    let interval_hours = settings_repository::get_setting(conn, INTERVAL_KEY)?
+        .and_then(|v| v.parse::<u32>().ok())
+        .unwrap_or(DEFAULT_INTERVAL_HOURS);
+    let folder = settings_repository::get_setting(conn, FOLDER_KEY)?.filter(|v| !v.is_empty());
+
+    Ok(BackupSettings {
+        enabled,
+        interval_hours,
+        folder,
+    })
+}
+
+/// 保存自动备份设置
+pub fn set_settings(conn: &Connection, settings: &BackupSettings) -> Result<(), AppError> {
+    if settings.interval_hours == 0 {
+        return Err(AppError::ValidationError("备份间隔至少为 1 小时".to_string()));
+    }
+    let folder = settings.folder.as_deref().map(str::trim).filter(|f| !f.is_empty());
+    if let Some(folder) = folder {
+        if !Path::new(folder).is_absolute() {
+            return Err(AppError::ValidationError("备份目录必须是绝对路径".to_string()));
+        }
+    }
+
+    settings_repository::set_setting(conn, ENABLED_KEY, if settings.enabled { "1" } else { "0" })?;
+    settings_repository::set_setting(conn, INTERVAL_KEY, &settings.interval_hours.to_string())?;
+    settings_repository::set_setting(conn, FOLDER_KEY, folder.unwrap_or(""))
+}
+
+/// 写入一份带时间戳的备份并按策略轮换，返回新备份的信息
+pub fn create_backup(conn: &Connection, dir: &Path) -> Result<BackupFileDto, AppError> {
+    fs::create_dir_all(dir)?;
+
+    let now = Local::now().naive_local();
+    let file_name = format!("{}{}{}", FILE_PREFIX, now.format(TIMESTAMP_FORMAT), FILE_SUFFIX);
+    let path = dir.join(&file_name);
+    backup_service::export_to_file(conn, &path.to_string_lossy(), None, None)?;
+
+    let removed = rotate(dir)?;
+    if removed > 0 {
+        info!("备份轮换已删除 {} 个旧备份", removed);
+    }
+
+    backup_info(&path).ok_or_else(|| AppError::Unknown("无法读取新备份".to_string()))
+}
+
+/// 列出目录中的备份，最新的在前；不符合命名规则的文件会被忽略
+pub fn list_backups(dir: &Path) -> Result<Vec<BackupFileDto>, AppError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut backups: Vec<BackupFileDto> = entries
+        .filter_map(|entry| backup_info(&entry.ok()?.path()))
+        .collect();
+    backups.sort_by(|a, b| b.file_name.cmp(&a.file_name));
+
+    Ok(backups)
+}
+
+/// 按“保留 7 天、4 周、12 个月”的策略删除多余的备份，返回删除的数量
+pub fn rotate(dir: &Path) -> Result<usize, AppError> {
+    // list_backups 只返回文件名可解析的备份，序号与时间一一对应
+    let backups = list_backups(dir)?;
+    let times: Vec<NaiveDateTime> = backups
+        .iter()
+        .filter_map(|b| parse_file_name(&b.file_name))
+        .collect();
+    let kept = select_kept(&times);
+
+    let mut removed = 0;
+    for (index, backup) in backups.iter().enumerate() {
+        if !kept.contains(&index) {
+            fs::remove_file(&backup.path)?;
+            removed += 1;
+        }
+    }
+
+    Ok(removed)
+}
+
+/// 从备份恢复（覆盖现有数据）
+///
+/// 只接受备份目录中的文件名。恢复前先为当前数据写一份备份，以便撤销。
+pub fn restore_backup(conn: &mut Connection, dir: &Path, file_name: &str) -> Result<ImportReport, AppError> {
+    if parse_file_name(file_name).is_none() || file_name.contains(['/', '\\']) {
+        return Err(AppError::ValidationError(format!("无效的备份文件名: {}", file_name)));
+    }
+    let path = dir.join(file_name);
+    if !path.is_file() {
+        return Err(AppError::NotFound(format!("备份 {} 不存在", file_name)));
+    }
+
+    // 先校验备份，避免在无效文件上覆盖数据
+    let data = backup_service::read_backup_file(&path, None)?;
+
+    let safety = create_backup(conn, dir)?;
+    info!("恢复备份前已备份当前数据: {}", safety.file_name);
+
+    backup_service::import_export_data(conn, data, ImportMode::Replace, ConflictPolicy::Skip)
+}
+
+/// 是否已到下次自动备份的时间
+pub fn is_due(settings: &BackupSettings, dir: &Path) -> Result<bool, AppError> {
+    if !settings.enabled {
+        return Ok(false);
+    }
+
+    let latest = list_backups(dir)?
+        .first()
+        .and_then(|b| parse_file_name(&b.file_name));
+    let interval = chrono::Duration::hours(i64::from(settings.interval_hours));
+
+    Ok(latest.is_none_or(|t| t + interval <= Local::now().naive_local()))
+}
+
+/// 备份目录：设置中指定的目录，或应用数据目录下的 backups/
+pub fn backup_dir(app: &AppHandle, settings: &BackupSettings) -> Result<PathBuf, AppError> {
+    if let Some(folder) = &settings.folder {
+        return Ok(PathBuf::from(folder));
+    }
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::Unknown(format!("无法获取应用数据目录: {}", e)))?;
+
+    Ok(app_data_dir.join("backups"))
+}
+
+/// 启动后台定时任务线程：每隔一段时间检查设置，到期时写入备份，并清理超过保留期的回收站条目
+///
+/// 启动时立即执行一次，应用长时间运行时回收站也会按保留期清理。
+pub fn start_scheduler(app: AppHandle) {
+    thread::spawn(move || loop {
+        if let Err(e) = backup_if_due(&app) {
+            error!("自动备份失败: {:?}", e);
+        }
+        if let Err(e) = purge_expired_trash(&app) {
+            error!("清理回收站失败: {:?}", e);
+        }
+        thread::sleep(CHECK_INTERVAL);
+    });
+}
+
+/// 退出时备份（已启用自动备份时）
+pub fn backup_on_exit(app: &AppHandle) {
+    let result = (|| {
+        let db = app.state::<DbState>();
+        let conn = db.read().map_err(|e| AppError::Unknown(e.message))?;
+        let settings = get_settings(&conn)?;
+        if !settings.enabled {
+            return Ok(());
+        }
+
+        let backup = create_backup(&conn, &backup_dir(app, &settings)?)?;
+        info!("退出前已备份: {}", backup.file_name);
+        Ok::<(), AppError>(())
+    })();
+
+    if let Err(e) = result {
+        error!("退出前备份失败: {:?}", e);
+    }
+}
+
+fn backup_if_due(app: &AppHandle) -> Result<(), AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.read().map_err(|e| AppError::Unknown(e.message))?;
+
+    let settings = get_settings(&conn)?;
+    let dir = backup_dir(app, &settings)?;
+    if is_due(&settings, &dir)? {
+        let backup = create_backup(&conn, &dir)?;
+        info!("自动备份完成: {}", backup.file_name);
+    }
+
+    Ok(())
+}
+
+fn purge_expired_trash(app: &AppHandle) -> Result<(), AppError> {
+    let db = app.state::<DbState>();
+    let conn = db.write().map_err(|e| AppError::Unknown(e.message))?;
+
+    let purged = trash_service::purge_expired(&conn)?;
+    if purged > 0 {
+        info!("已清理过期回收站条目: {}", purged);
+    }
+
+    Ok(())
+}
+
+/// 选出需要保留的备份序号（输入按时间从新到旧排列）
+fn select_kept(times: &[NaiveDateTime]) -> HashSet<usize> {
+    let mut kept = HashSet::new();
+    let mut days = HashSet::new();
+    let mut weeks = HashSet::new();
+    let mut months = HashSet::new();
+
+    for (index, time) in times.iter().enumerate() {
+        let date = time.date();
+        let week = date.iso_week();
+
+        // 每个时间段内只保留最新的一份
+        if days.len() < KEEP_DAILY && days.insert(date) {
+            kept.insert(index);
+        }
+        if weeks.len() < KEEP_WEEKLY && weeks.insert((week.year(), week.week())) {
+            kept.insert(index);
+        }
+        if months.len() < KEEP_MONTHLY && months.insert((date.year(), date.month())) {
+            kept.insert(index);
+        }
+    }
+
+    kept
+}
+
+fn parse_file_name(file_name: &str) -> Option<NaiveDateTime> {
+    let timestamp = file_name.strip_prefix(FILE_PREFIX)?.strip_suffix(FILE_SUFFIX)?;
+    NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT).ok()
+}
+
+fn backup_info(path: &Path) -> Option<BackupFileDto> {
+    let file_name = path.file_name()?.to_string_lossy().to_string();
+    let time = parse_file_name(&file_name)?;
+    let metadata = fs::metadata(path).ok().filter(|m| m.is_file())?;
+    let created_at = Local
+        .from_local_datetime(&time)
+        .earliest()
+        .map(|t| t.to_rfc3339())
+        .unwrap_or_else(|| time.to_string());
+
+    Some(BackupFileDto {
+        file_name,
+        path: path.to_string_lossy().to_string(),
+        created_at,
+        size: metadata.len(),
+    })
+}
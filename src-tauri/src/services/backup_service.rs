@@ -1,8 +1,23 @@
 use crate::error::AppError;
-use crate::models::{ProjectDto, PromptEntryDto, TaskDto};
-use rusqlite::{params, Connection};
+use crate::models::{
+    ConflictPolicy, ExportScope, ImportMode, ImportReport, ImportTarget, ImportTargetKind, ProjectDto,
+    PromptEntryDto, TaskDto,
+};
+use crate::repositories::prompt_repository;
+use crate::repositories::tag_repository::{self, TAGS_JSON_COLUMN};
+use crate::services::{archive_service, encryption_service, schema_service};
+use crate::utils::id::new_uuid;
+use crate::utils::pinyin::pinyin_keys;
+use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs;
+use std::path::Path;
+
+/// 当前导出格式版本（2：增加 uuid 字段）
+///
+/// 修改格式时同步更新 `schemas/export.schema.json`，并在 schema_service 中补充从上一版本升级的步骤。
+pub const EXPORT_VERSION: i32 = 2;
 
 /// 导出数据结构
 #[derive(Debug, Serialize, Deserialize)]
@@ -14,6 +29,9 @@ pub struct ExportData {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ExportProject {
+    /// 全局唯一 ID；旧版本导出文件中没有该字段
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
     pub name: String,
     pub created_at: String,
     pub updated_at: Option<String>,
@@ -22,6 +40,9 @@ pub struct ExportProject {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ExportTask {
+    /// 全局唯一 ID；旧版本导出文件中没有该字段
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
     pub name: String,
     pub description: Option<String>,
     pub created_at: String,
@@ -31,6 +52,9 @@ pub struct ExportTask {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ExportPrompt {
+    /// 全局唯一 ID；旧版本导出文件中没有该字段
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
     pub title: Option<String>,
     pub content: String,
     pub tags: Option<Vec<String>>,
@@ -39,13 +63,50 @@ pub struct ExportPrompt {
     pub updated_at: Option<String>,
 }
 
-/// 导出数据到 JSON 文件
-pub fn export_to_file(conn: &Connection, path: &str) -> Result<(), AppError> {
+/// 导出数据到文件
+///
+/// 指定 `scope` 时只导出范围内的数据。
+/// 提供口令时写入加密的压缩包；否则扩展名为 `.zip` 时写入带清单和校验和的压缩包，其余写入 JSON。
+/// 先写到同目录的临时文件再改名，避免中途失败留下不完整的备份。
+pub fn export_to_file(
+    conn: &Connection,
+    path: &str,
+    passphrase: Option<&str>,
+    scope: Option<&ExportScope>,
+) -> Result<(), AppError> {
+    let export_data = match scope {
+        Some(scope) => collect_scoped_export_data(conn, scope)?,
+        None => collect_export_data(conn)?,
+    };
+
+    let bytes = match passphrase {
+        Some(passphrase) => {
+            encryption_service::encrypt(&archive_service::archive_bytes(&export_data)?, passphrase)?
+        }
+        None if path.to_lowercase().ends_with(".zip") => archive_service::archive_bytes(&export_data)?,
+        None => serde_json::to_vec_pretty(&export_data)?,
+    };
+
+    let path = Path::new(path);
+    let temp_path = path.with_extension("tmp");
+    let result = fs::write(&temp_path, &bytes).and_then(|_| fs::rename(&temp_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+
+    Ok(result?)
+}
+
+/// 读取全部未删除的数据，组装为导出结构
+pub fn collect_export_data(conn: &Connection) -> Result<ExportData, AppError> {
     let now = chrono::Utc::now().to_rfc3339();
 
+    // 在同一个读事务中完成所有查询，保证导出的是一致的快照
+    let conn = conn.unchecked_transaction()?;
+
     // 获取所有项目
     let mut projects_stmt = conn.prepare(
-        "SELECT id, name, created_at, updated_at FROM projects ORDER BY created_at",
+        "SELECT id, name, created_at, updated_at, uuid FROM projects WHERE deleted_at IS NULL ORDER BY created_at",
     )?;
 
     let projects: Vec<(i64, ProjectDto)> = projects_stmt
@@ -54,6 +115,7 @@ pub fn export_to_file(conn: &Connection, path: &str) -> Result<(), AppError> {
                 row.get::<_, i64>(0)?,
                 ProjectDto {
                     id: row.get(0)?,
+                    uuid: row.get(4)?,
                     name: row.get(1)?,
                     created_at: row.get(2)?,
                     updated_at: row.get(3)?,
@@ -67,8 +129,8 @@ pub fn export_to_file(conn: &Connection, path: &str) -> Result<(), AppError> {
     for (project_id, project) in projects {
         // 获取项目下的任务
         let mut tasks_stmt = conn.prepare(
-            "SELECT id, name, description, created_at, updated_at 
-             FROM tasks WHERE project_id = ?1 ORDER BY created_at",
+            "SELECT id, name, description, created_at, updated_at, uuid 
+             FROM tasks WHERE project_id = ?1 AND deleted_at IS NULL ORDER BY created_at",
         )?;
 
         let tasks: Vec<(i64, TaskDto)> = tasks_stmt
@@ -77,6 +139,7 @@ pub fn export_to_file(conn: &Connection, path: &str) -> Result<(), AppError> {
                     row.get::<_, i64>(0)?,
                     TaskDto {
                         id: row.get(0)?,
+                        uuid: row.get(5)?,
                         project_id,
                         name: row.get(1)?,
                         description: row.get(2)?,
@@ -91,18 +154,21 @@ pub fn export_to_file(conn: &Connection, path: &str) -> Result<(), AppError> {
 
         for (task_id, task) in tasks {
             // 获取任务下的提示词
-            let mut prompts_stmt = conn.prepare(
-                "SELECT title, content, tags, model, created_at, updated_at 
-                 FROM prompt_entries WHERE task_id = ?1 ORDER BY created_at",
-            )?;
+            let mut prompts_stmt = conn.prepare(&format!(
+                "SELECT title, content, {}, model, created_at, updated_at, uuid 
+                 FROM prompt_entries WHERE task_id = ?1 AND deleted_at IS NULL ORDER BY created_at",
+                TAGS_JSON_COLUMN
+            ))?;
 
             let prompts: Vec<ExportPrompt> = prompts_stmt
                 .query_map(params![task_id], |row| {
                     let tags_json: Option<String> = row.get(2)?;
-                    let tags: Option<Vec<String>> =
-                        tags_json.and_then(|s| serde_json::from_str(&s).ok());
+                    let tags: Option<Vec<String>> = tags_json
+                        .and_then(|s| serde_json::from_str::<Vec<String>>(&s).ok())
+                        .filter(|t| !t.is_empty());
 
                     Ok(ExportPrompt {
+                        uuid: row.get(6)?,
                         title: row.get(0)?,
                         content: row.get(1)?,
                         tags,
@@ -114,6 +180,7 @@ pub fn export_to_file(conn: &Connection, path: &str) -> Result<(), AppError> {
                 .collect::<Result<Vec<_>, _>>()?;
 
             export_tasks.push(ExportTask {
+                uuid: Some(task.uuid),
                 name: task.name,
                 description: task.description,
                 created_at: task.created_at,
@@ -123,6 +190,7 @@ pub fn export_to_file(conn: &Connection, path: &str) -> Result<(), AppError> {
         }
 
         export_projects.push(ExportProject {
+            uuid: Some(project.uuid),
             name: project.name,
             created_at: project.created_at,
             updated_at: project.updated_at,
@@ -130,77 +198,412 @@ pub fn export_to_file(conn: &Connection, path: &str) -> Result<(), AppError> {
         });
     }
 
-    let export_data = ExportData {
-        version: 1,
+    Ok(ExportData {
+        version: EXPORT_VERSION,
         exported_at: now,
         projects: export_projects,
-    };
+    })
+}
 
-    let json = serde_json::to_string_pretty(&export_data)?;
-    fs::write(path, json)?;
+/// 按范围组装导出结构
+///
+/// 选中的项目、任务整体导出；单独选中的提示词连同所属任务和项目导出，但任务下只包含选中的提示词。
+pub fn collect_scoped_export_data(conn: &Connection, scope: &ExportScope) -> Result<ExportData, AppError> {
+    if scope.project_ids.is_empty() && scope.task_ids.is_empty() && scope.prompt_ids.is_empty() {
+        return Err(AppError::ValidationError("导出范围不能为空".to_string()));
+    }
 
-    Ok(())
+    let project_uuids = lookup_uuids(conn, "projects", &scope.project_ids, "项目")?;
+    let task_uuids = lookup_uuids(conn, "tasks", &scope.task_ids, "任务")?;
+    let prompt_uuids = lookup_uuids(conn, "prompt_entries", &scope.prompt_ids, "提示词记录")?;
+    let selected = |set: &HashSet<String>, uuid: &Option<String>| uuid.as_ref().is_some_and(|u| set.contains(u));
+
+    let mut data = collect_export_data(conn)?;
+    data.projects.retain_mut(|project| {
+        if selected(&project_uuids, &project.uuid) {
+            return true;
+        }
+
+        project.tasks.retain_mut(|task| {
+            if selected(&task_uuids, &task.uuid) {
+                return true;
+            }
+
+            task.prompts.retain(|prompt| selected(&prompt_uuids, &prompt.uuid));
+            !task.prompts.is_empty()
+        });
+        !project.tasks.is_empty()
+    });
+
+    Ok(data)
 }
 
-/// 从 JSON 文件导入数据（覆盖模式）
-pub fn import_from_file(conn: &mut Connection, path: &str) -> Result<(), AppError> {
-    let json = fs::read_to_string(path)?;
-    let export_data: ExportData = serde_json::from_str(&json)?;
+/// 查询未删除记录的 uuid，任一 ID 不存在时返回 `NotFound`
+fn lookup_uuids(conn: &Connection, table: &str, ids: &[i64], label: &str) -> Result<HashSet<String>, AppError> {
+    let mut stmt = conn.prepare(&format!("SELECT uuid FROM {} WHERE id = ?1 AND deleted_at IS NULL", table))?;
+
+    ids.iter()
+        .map(|id| {
+            stmt.query_row(params![id], |row| row.get(0))
+                .optional()?
+                .ok_or_else(|| AppError::NotFound(format!("{} {} 不存在", label, id)))
+        })
+        .collect()
+}
+
+/// 读取备份文件，自动识别加密文件、压缩包和 JSON
+///
+/// 加密文件先解密（未提供口令时返回 `PassphraseRequired`），压缩包先按清单校验；
+/// 全部通过后才返回数据，调用方再写入数据库。
+pub fn read_backup_file(path: &Path, passphrase: Option<&str>) -> Result<ExportData, AppError> {
+    let mut bytes = fs::read(path)?;
+
+    if encryption_service::is_encrypted(&bytes) {
+        let passphrase = passphrase.ok_or(AppError::PassphraseRequired)?;
+        bytes = encryption_service::decrypt(&bytes, passphrase)?;
+    }
+    if archive_service::is_archive(&bytes) {
+        return archive_service::read_archive(&bytes);
+    }
+
+    schema_service::parse_export_data(&bytes)
+}
 
+/// 将导出结构写入数据库
+///
+/// 覆盖模式先清空现有数据；合并模式优先按 uuid、其次按名称匹配已有的项目和任务，
+/// 同一任务下 uuid 或内容相同的提示词视为重复，按 `policy` 处理。
+pub fn import_export_data(
+    conn: &mut Connection,
+    export_data: ExportData,
+    mode: ImportMode,
+    policy: ConflictPolicy,
+) -> Result<ImportReport, AppError> {
     // 开启事务
     let tx = conn.transaction()?;
 
-    // 清空现有数据（按顺序删除，遵循外键约束）
-    tx.execute_batch(
-        r#"
-        DELETE FROM prompt_entries;
-        DELETE FROM tasks;
-        DELETE FROM projects;
-        "#,
-    )?;
+    if mode == ImportMode::Replace {
+        // 清空现有数据（按顺序删除，遵循外键约束）
+        tx.execute_batch(
+            r#"
+            DELETE FROM prompt_entries;
+            DELETE FROM tasks;
+            DELETE FROM projects;
+            DELETE FROM tags;
+            "#,
+        )?;
+    }
+
+    let mut report = ImportReport::default();
 
     // 导入项目
     for project in export_data.projects {
-        tx.execute(
-            "INSERT INTO projects (name, created_at, updated_at) VALUES (?1, ?2, ?3)",
-            params![project.name, project.created_at, project.updated_at],
-        )?;
-
-        let project_id = tx.last_insert_rowid();
+        let project_id = match find_project_id(&tx, project.uuid.as_deref(), &project.name)? {
+            Some(id) => {
+                report.projects_merged += 1;
+                id
+            }
+            None => {
+                report.projects_added += 1;
+                insert_project(&tx, &project)?
+            }
+        };
 
         // 导入任务
         for task in project.tasks {
-            tx.execute(
-                "INSERT INTO tasks (project_id, name, description, created_at, updated_at) 
-                 VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![project_id, task.name, task.description, task.created_at, task.updated_at],
-            )?;
-
-            let task_id = tx.last_insert_rowid();
-
-            // 导入提示词
-            for prompt in task.prompts {
-                let tags_json = prompt.tags.map(|t| serde_json::to_string(&t).unwrap_or_default());
-
-                tx.execute(
-                    "INSERT INTO prompt_entries (task_id, title, content, tags, model, created_at, updated_at) 
-                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-                    params![
-                        task_id,
-                        prompt.title,
-                        prompt.content,
-                        tags_json,
-                        prompt.model,
-                        prompt.created_at,
-                        prompt.updated_at
-                    ],
-                )?;
-            }
+            import_task(&tx, project_id, task, policy, false, &mut report)?;
         }
     }
 
     // 提交事务
     tx.commit()?;
 
+    Ok(report)
+}
+
+/// 将导出结构并入指定的已有项目或任务
+///
+/// 导入到项目时，备份中各项目下的任务都并入该项目；导入到任务时，所有提示词都并入该任务。
+/// 任务和提示词的匹配规则与合并导入相同。
+pub fn import_into_target(
+    conn: &mut Connection,
+    export_data: ExportData,
+    target: ImportTarget,
+    policy: ConflictPolicy,
+) -> Result<ImportReport, AppError> {
+    let tasks = export_data.projects.into_iter().flat_map(|p| p.tasks);
+    let tx = conn.transaction()?;
+    let mut report = ImportReport::default();
+
+    match target.kind {
+        ImportTargetKind::Project => {
+            lookup_uuids(&tx, "projects", &[target.id], "项目")?;
+            for task in tasks {
+                import_task(&tx, target.id, task, policy, false, &mut report)?;
+            }
+        }
+        ImportTargetKind::Task => {
+            lookup_uuids(&tx, "tasks", &[target.id], "任务")?;
+            for prompt in tasks.flat_map(|t| t.prompts) {
+                import_prompt(&tx, target.id, prompt, policy, false, &mut report)?;
+            }
+        }
+    }
+
+    tx.commit()?;
+
+    Ok(report)
+}
+
+/// 将对话导出中的任务并入已有项目，任务和提示词的匹配规则与合并导入相同
+///
+/// 对话导出会重复导入，uuid 已存在于其他位置（回收站或其他任务）的提示词视为导入过，计入跳过数，
+/// 避免删除或移动过的消息以新 uuid 再次出现。
+pub fn import_tasks_into_project(
+    conn: &mut Connection,
+    tasks: Vec<ExportTask>,
+    project_id: i64,
+    policy: ConflictPolicy,
+) -> Result<ImportReport, AppError> {
+    let tx = conn.transaction()?;
+    lookup_uuids(&tx, "projects", &[project_id], "项目")?;
+
+    let mut report = ImportReport::default();
+    for task in tasks {
+        import_task(&tx, project_id, task, policy, true, &mut report)?;
+    }
+
+    tx.commit()?;
+
+    Ok(report)
+}
+
+/// 导入任务及其提示词：按 uuid 或名称匹配项目下已有的任务，没有时新建
+///
+/// `skip_known` 为 true 时，uuid 已存在于数据库任意位置的提示词直接跳过。
+fn import_task(
+    conn: &Connection,
+    project_id: i64,
+    task: ExportTask,
+    policy: ConflictPolicy,
+    skip_known: bool,
+    report: &mut ImportReport,
+) -> Result<(), AppError> {
+    let task_id = match find_task_id(conn, project_id, task.uuid.as_deref(), &task.name)? {
+        Some(id) => {
+            report.tasks_merged += 1;
+            id
+        }
+        // 提示词都已在其他位置（如回收站或其他任务）时不新建空任务
+        None if skip_known && !task.prompts.is_empty() && all_prompts_exist(conn, &task.prompts)? => {
+            report.prompts_skipped += task.prompts.len();
+            return Ok(());
+        }
+        None => {
+            report.tasks_added += 1;
+            insert_task(conn, project_id, &task)?
+        }
+    };
+
+    for prompt in task.prompts {
+        import_prompt(conn, task_id, prompt, policy, skip_known, report)?;
+    }
+
     Ok(())
 }
+
+/// 导入提示词：任务下 uuid 或内容相同的记录视为重复，按 `policy` 处理
+///
+/// `skip_known` 为 true 且任务下没有匹配时，uuid 已存在于其他位置（回收站或其他任务）也计入跳过数。
+fn import_prompt(
+    conn: &Connection,
+    task_id: i64,
+    prompt: ExportPrompt,
+    policy: ConflictPolicy,
+    skip_known: bool,
+    report: &mut ImportReport,
+) -> Result<(), AppError> {
+    let existing = match find_prompt_id(conn, task_id, prompt.uuid.as_deref(), &prompt.content)? {
+        Some(id) => Some(prompt_repository::get_prompt(conn, id)?),
+        None => None,
+    };
+
+    match existing {
+        None if skip_known && uuid_exists(conn, "prompt_entries", prompt.uuid.as_deref())? => {
+            report.prompts_skipped += 1;
+        }
+        None => {
+            insert_prompt(conn, task_id, &prompt)?;
+            report.prompts_added += 1;
+        }
+        // 各字段都相同的记录无需处理
+        Some(current) if is_same_prompt(&current, &prompt) => {
+            report.prompts_skipped += 1;
+        }
+        Some(current) => match policy {
+            ConflictPolicy::Skip => report.prompts_skipped += 1,
+            ConflictPolicy::Overwrite => {
+                prompt_repository::write_prompt(
+                    conn,
+                    &current,
+                    prompt.title.as_deref(),
+                    &prompt.content,
+                    prompt.tags.as_deref(),
+                    prompt.model.as_deref(),
+                )?;
+                report.prompts_updated += 1;
+            }
+            ConflictPolicy::KeepBoth => {
+                insert_prompt(conn, task_id, &prompt)?;
+                report.prompts_added += 1;
+            }
+        },
+    }
+
+    Ok(())
+}
+
+/// 按 uuid 或名称查找未删除的项目，uuid 匹配优先
+fn find_project_id(conn: &Connection, uuid: Option<&str>, name: &str) -> Result<Option<i64>, AppError> {
+    conn.query_row(
+        "SELECT id FROM projects WHERE (uuid = ?1 OR name = ?2) AND deleted_at IS NULL
+         ORDER BY uuid IS NOT ?1, id LIMIT 1",
+        params![uuid, name],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(Into::into)
+}
+
+/// 按 uuid 或名称查找项目下未删除的任务，uuid 匹配优先
+fn find_task_id(
+    conn: &Connection,
+    project_id: i64,
+    uuid: Option<&str>,
+    name: &str,
+) -> Result<Option<i64>, AppError> {
+    conn.query_row(
+        "SELECT id FROM tasks WHERE project_id = ?1 AND (uuid = ?2 OR name = ?3) AND deleted_at IS NULL
+         ORDER BY uuid IS NOT ?2, id LIMIT 1",
+        params![project_id, uuid, name],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(Into::into)
+}
+
+/// 按 uuid 或内容查找任务下未删除的提示词，uuid 匹配优先
+fn find_prompt_id(
+    conn: &Connection,
+    task_id: i64,
+    uuid: Option<&str>,
+    content: &str,
+) -> Result<Option<i64>, AppError> {
+    conn.query_row(
+        "SELECT id FROM prompt_entries WHERE task_id = ?1 AND (uuid = ?2 OR content = ?3) AND deleted_at IS NULL
+         ORDER BY uuid IS NOT ?2, id LIMIT 1",
+        params![task_id, uuid, content],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(Into::into)
+}
+
+/// 导入记录使用的 uuid：沿用导出文件中的值，缺失或已被占用（如回收站中的同一条目）时生成新的
+fn resolve_uuid(conn: &Connection, table: &str, uuid: Option<&str>) -> Result<String, AppError> {
+    match uuid {
+        Some(uuid) if !uuid_exists(conn, table, Some(uuid))? => Ok(uuid.to_string()),
+        _ => Ok(new_uuid()),
+    }
+}
+
+/// 表中是否已有该 uuid 的记录，包括回收站中的
+fn uuid_exists(conn: &Connection, table: &str, uuid: Option<&str>) -> Result<bool, AppError> {
+    let Some(uuid) = uuid else {
+        return Ok(false);
+    };
+
+    conn.query_row(
+        &format!("SELECT EXISTS(SELECT 1 FROM {} WHERE uuid = ?1)", table),
+        params![uuid],
+        |row| row.get(0),
+    )
+    .map_err(Into::into)
+}
+
+/// 提示词是否都带有 uuid 且均已存在
+fn all_prompts_exist(conn: &Connection, prompts: &[ExportPrompt]) -> Result<bool, AppError> {
+    for prompt in prompts {
+        if !uuid_exists(conn, "prompt_entries", prompt.uuid.as_deref())? {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// 导入的提示词与现有记录是否完全一致
+fn is_same_prompt(current: &PromptEntryDto, prompt: &ExportPrompt) -> bool {
+    current.title == prompt.title
+        && current.content == prompt.content
+        && current.tags.as_deref().unwrap_or(&[]) == prompt.tags.as_deref().unwrap_or(&[])
+        && current.model == prompt.model
+}
+
+fn insert_project(conn: &Connection, project: &ExportProject) -> Result<i64, AppError> {
+    let keys = pinyin_keys(&project.name);
+    let uuid = resolve_uuid(conn, "projects", project.uuid.as_deref())?;
+    conn.execute(
+        "INSERT INTO projects (uuid, name, name_pinyin, name_initials, created_at, updated_at) 
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![uuid, project.name, keys.full, keys.initials, project.created_at, project.updated_at],
+    )?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+fn insert_task(conn: &Connection, project_id: i64, task: &ExportTask) -> Result<i64, AppError> {
+    let keys = pinyin_keys(&task.name);
+    let uuid = resolve_uuid(conn, "tasks", task.uuid.as_deref())?;
+    conn.execute(
+        "INSERT INTO tasks (uuid, project_id, name, name_pinyin, name_initials, description, created_at, updated_at) 
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            uuid,
+            project_id,
+            task.name,
+            keys.full,
+            keys.initials,
+            task.description,
+            task.created_at,
+            task.updated_at
+        ],
+    )?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+fn insert_prompt(conn: &Connection, task_id: i64, prompt: &ExportPrompt) -> Result<i64, AppError> {
+    let keys = pinyin_keys(prompt.title.as_deref().unwrap_or(""));
+    let uuid = resolve_uuid(conn, "prompt_entries", prompt.uuid.as_deref())?;
+    conn.execute(
+        "INSERT INTO prompt_entries (uuid, task_id, title, title_pinyin, title_initials, content, model, created_at, updated_at) 
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            uuid,
+            task_id,
+            prompt.title,
+            keys.full,
+            keys.initials,
+            prompt.content,
+            prompt.model,
+            prompt.created_at,
+            prompt.updated_at
+        ],
+    )?;
+
+    let prompt_id = conn.last_insert_rowid();
+    tag_repository::set_prompt_tags(conn, prompt_id, prompt.tags.as_deref().unwrap_or(&[]))?;
+
+    Ok(prompt_id)
+}
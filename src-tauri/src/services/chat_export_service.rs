@@ -0,0 +1,37 @@
+use crate::error::AppError;
+use crate::services::archive_service;
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::Path;
+use zip::result::ZipError;
+use zip::ZipArchive;
+
+/// 官方导出压缩包中的对话文件名（ChatGPT 和 Claude.ai 相同）
+const CONVERSATIONS_NAME: &str = "conversations.json";
+/// 对话没有标题时使用的任务名
+pub const UNTITLED_NAME: &str = "未命名对话";
+
+/// 读取对话文件；官方导出的压缩包中取出 conversations.json
+pub fn read_conversations_file(path: &Path) -> Result<Vec<u8>, AppError> {
+    let bytes = fs::read(path)?;
+    if !archive_service::is_archive(&bytes) {
+        return Ok(bytes);
+    }
+
+    let mut zip = ZipArchive::new(Cursor::new(bytes))
+        .map_err(|e| AppError::ValidationError(format!("无法读取压缩包: {}", e)))?;
+    let mut file = zip.by_name(CONVERSATIONS_NAME).map_err(|e| match e {
+        ZipError::FileNotFound => AppError::ValidationError(format!("压缩包中没有 {}", CONVERSATIONS_NAME)),
+        e => AppError::ValidationError(format!("无法读取 {}: {}", CONVERSATIONS_NAME, e)),
+    })?;
+
+    let mut json = Vec::new();
+    file.read_to_end(&mut json)?;
+
+    Ok(json)
+}
+
+/// 导出文件中的 ID 一般是 UUID，沿用后重复导入时能匹配到同一条目
+pub fn is_uuid(id: &str) -> bool {
+    uuid::Uuid::parse_str(id).is_ok()
+}
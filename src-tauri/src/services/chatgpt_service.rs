@@ -0,0 +1,244 @@
+use crate::error::AppError;
+use crate::models::{ConflictPolicy, ImportFailure, ImportReport};
+use crate::services::backup_service::{self, ExportPrompt, ExportTask};
+use crate::services::chat_export_service::{is_uuid, read_conversations_file, UNTITLED_NAME};
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+#[derive(Debug, Deserialize)]
+struct Conversation {
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    create_time: Option<f64>,
+    #[serde(default)]
+    update_time: Option<f64>,
+    #[serde(default)]
+    conversation_id: Option<String>,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    default_model_slug: Option<String>,
+    #[serde(default)]
+    mapping: HashMap<String, Node>,
+}
+
+/// 消息树中的节点，`children` 中多于一个时表示编辑或重新生成产生的分支
+#[derive(Debug, Deserialize)]
+struct Node {
+    #[serde(default)]
+    message: Option<Message>,
+    #[serde(default)]
+    parent: Option<String>,
+    #[serde(default)]
+    children: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Message {
+    #[serde(default)]
+    id: Option<String>,
+    author: Author,
+    #[serde(default)]
+    content: Option<Content>,
+    #[serde(default)]
+    create_time: Option<f64>,
+    #[serde(default)]
+    metadata: Option<Metadata>,
+}
+
+impl Message {
+    /// 系统插入的上下文（如自定义指令）在界面上不显示
+    fn is_hidden(&self) -> bool {
+        self.metadata
+            .as_ref()
+            .is_some_and(|m| m.is_visually_hidden_from_conversation)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Author {
+    role: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Content {
+    #[serde(default)]
+    content_type: String,
+    /// 文本为字符串，图片等附件为对象
+    #[serde(default)]
+    parts: Vec<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Metadata {
+    #[serde(default)]
+    model_slug: Option<String>,
+    #[serde(default)]
+    is_visually_hidden_from_conversation: bool,
+}
+
+/// 导入 ChatGPT 数据导出（`conversations.json` 或包含它的压缩包）
+///
+/// 每个对话对应指定项目下的一个任务，对话中用户发送的消息作为提示词；编辑产生的分支也会导入。
+/// 短于 `min_length` 个字符的消息计入跳过数。无法解析的对话记入报告，不中断导入。
+pub fn import_conversations(
+    conn: &mut Connection,
+    path: &Path,
+    project_id: i64,
+    min_length: usize,
+    policy: ConflictPolicy,
+) -> Result<ImportReport, AppError> {
+    let conversations: Vec<Value> = serde_json::from_slice(&read_conversations_file(path)?)
+        .map_err(|e| AppError::ValidationError(format!("不是有效的 ChatGPT 导出文件: {}", e)))?;
+
+    let now = Utc::now().to_rfc3339();
+    let mut tasks = Vec::new();
+    let mut failed_files = Vec::new();
+    let mut too_short = 0;
+
+    for (index, value) in conversations.into_iter().enumerate() {
+        match serde_json::from_value::<Conversation>(value) {
+            Ok(conversation) => {
+                let (task, skipped) = conversation_task(conversation, min_length, &now);
+                too_short += skipped;
+                if !task.prompts.is_empty() {
+                    tasks.push(task);
+                }
+            }
+            Err(e) => failed_files.push(ImportFailure {
+                path: format!("第 {} 个对话", index + 1),
+                reason: e.to_string(),
+            }),
+        }
+    }
+
+    let mut report = backup_service::import_tasks_into_project(conn, tasks, project_id, policy)?;
+    report.prompts_skipped += too_short;
+    report.failed_files = failed_files;
+
+    Ok(report)
+}
+
+/// 将一个对话转换为任务，返回任务和因过短而跳过的消息数
+fn conversation_task(conversation: Conversation, min_length: usize, now: &str) -> (ExportTask, usize) {
+    let created_at = conversation
+        .create_time
+        .and_then(timestamp)
+        .unwrap_or_else(|| now.to_string());
+    let mut prompts = Vec::new();
+    let mut skipped = 0;
+
+    for node_id in walk(&conversation.mapping) {
+        let node = &conversation.mapping[node_id];
+        let Some(message) = node.message.as_ref().filter(|m| m.author.role == "user" && !m.is_hidden()) else {
+            continue;
+        };
+        let Some(content) = message.content.as_ref().and_then(message_text) else {
+            continue;
+        };
+        if content.chars().count() < min_length {
+            skipped += 1;
+            continue;
+        }
+
+        // 用户消息一般不带模型信息，取其回复所用的模型
+        let model = node
+            .children
+            .iter()
+            .filter_map(|id| conversation.mapping.get(id)?.message.as_ref())
+            .chain(std::iter::once(message))
+            .find_map(|m| m.metadata.as_ref()?.model_slug.clone())
+            .or_else(|| conversation.default_model_slug.clone());
+
+        prompts.push(ExportPrompt {
+            uuid: message.id.clone().filter(|id| is_uuid(id)),
+            title: None,
+            content,
+            tags: None,
+            model,
+            created_at: message
+                .create_time
+                .and_then(timestamp)
+                .unwrap_or_else(|| created_at.clone()),
+            updated_at: None,
+        });
+    }
+
+    let name = conversation
+        .title
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .unwrap_or(UNTITLED_NAME)
+        .to_string();
+
+    let task = ExportTask {
+        uuid: conversation
+            .conversation_id
+            .or(conversation.id)
+            .filter(|id| is_uuid(id)),
+        name,
+        description: None,
+        updated_at: conversation.update_time.and_then(timestamp),
+        created_at,
+        prompts,
+    };
+
+    (task, skipped)
+}
+
+/// 从根节点开始深度优先遍历消息树，按对话顺序返回节点 ID
+fn walk(mapping: &HashMap<String, Node>) -> Vec<&str> {
+    let mut roots: Vec<&str> = mapping
+        .iter()
+        .filter(|(_, node)| node.parent.as_ref().is_none_or(|p| !mapping.contains_key(p)))
+        .map(|(id, _)| id.as_str())
+        .collect();
+    roots.sort();
+
+    let mut order = Vec::new();
+    let mut visited = HashSet::new();
+    let mut stack: Vec<&str> = roots.into_iter().rev().collect();
+
+    while let Some(id) = stack.pop() {
+        let Some(node) = mapping.get(id) else {
+            continue;
+        };
+        if !visited.insert(id) {
+            continue;
+        }
+
+        order.push(id);
+        stack.extend(node.children.iter().rev().map(String::as_str));
+    }
+
+    order
+}
+
+/// 取消息中的文本部分，图片等附件忽略
+fn message_text(content: &Content) -> Option<String> {
+    if content.content_type != "text" && content.content_type != "multimodal_text" {
+        return None;
+    }
+
+    let text = content
+        .parts
+        .iter()
+        .filter_map(Value::as_str)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let text = text.trim();
+
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// 导出文件中的时间为 Unix 秒（带小数）
+fn timestamp(seconds: f64) -> Option<String> {
+    let nanos = (seconds.fract() * 1e9) as u32;
+    DateTime::<Utc>::from_timestamp(seconds.trunc() as i64, nanos).map(|t| t.to_rfc3339())
+}
@@ -0,0 +1,160 @@
+use crate::error::AppError;
+use crate::models::{ConflictPolicy, ImportFailure, ImportReport};
+use crate::services::backup_service::{self, ExportPrompt, ExportTask};
+use crate::services::chat_export_service::{is_uuid, read_conversations_file, UNTITLED_NAME};
+use crate::services::markdown_service::normalize_timestamp;
+use chrono::Utc;
+use rusqlite::Connection;
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::Path;
+
+#[derive(Debug, Deserialize)]
+struct Conversation {
+    #[serde(default)]
+    uuid: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    created_at: Option<String>,
+    #[serde(default)]
+    updated_at: Option<String>,
+    /// 较新的导出中才有
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    chat_messages: Vec<ChatMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatMessage {
+    #[serde(default)]
+    uuid: Option<String>,
+    sender: String,
+    #[serde(default)]
+    text: Option<String>,
+    /// 较新的导出把正文拆成多个块，`text` 可能为空
+    #[serde(default)]
+    content: Vec<ContentBlock>,
+    #[serde(default)]
+    created_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContentBlock {
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(default)]
+    text: Option<String>,
+}
+
+/// 导入 Claude.ai 数据导出（`conversations.json` 或包含它的压缩包）
+///
+/// 每个对话对应指定项目下的一个任务，对话名为任务名，用户发送的消息作为提示词。
+/// 对话和消息沿用导出中的 uuid，对同一账号较新的导出重复导入时只会新增之前没有的消息。
+/// 短于 `min_length` 个字符的消息计入跳过数。无法解析的对话记入报告，不中断导入。
+pub fn import_conversations(
+    conn: &mut Connection,
+    path: &Path,
+    project_id: i64,
+    min_length: usize,
+    policy: ConflictPolicy,
+) -> Result<ImportReport, AppError> {
+    let conversations: Vec<Value> = serde_json::from_slice(&read_conversations_file(path)?)
+        .map_err(|e| AppError::ValidationError(format!("不是有效的 Claude.ai 导出文件: {}", e)))?;
+
+    let now = Utc::now().to_rfc3339();
+    let mut tasks = Vec::new();
+    let mut failed_files = Vec::new();
+    let mut too_short = 0;
+
+    for (index, value) in conversations.into_iter().enumerate() {
+        match serde_json::from_value::<Conversation>(value) {
+            Ok(conversation) => {
+                let (task, skipped) = conversation_task(conversation, min_length, &now);
+                too_short += skipped;
+                if !task.prompts.is_empty() {
+                    tasks.push(task);
+                }
+            }
+            Err(e) => failed_files.push(ImportFailure {
+                path: format!("第 {} 个对话", index + 1),
+                reason: e.to_string(),
+            }),
+        }
+    }
+
+    let mut report = backup_service::import_tasks_into_project(conn, tasks, project_id, policy)?;
+    report.prompts_skipped += too_short;
+    report.failed_files = failed_files;
+
+    Ok(report)
+}
+
+/// 将一个对话转换为任务，返回任务和因过短而跳过的消息数
+fn conversation_task(conversation: Conversation, min_length: usize, now: &str) -> (ExportTask, usize) {
+    let created_at = timestamp(conversation.created_at.as_deref()).unwrap_or_else(|| now.to_string());
+    let mut prompts = Vec::new();
+    let mut skipped = 0;
+
+    for message in conversation.chat_messages.iter().filter(|m| m.sender == "human") {
+        let Some(content) = message_text(message) else {
+            continue;
+        };
+        if content.chars().count() < min_length {
+            skipped += 1;
+            continue;
+        }
+
+        prompts.push(ExportPrompt {
+            uuid: message.uuid.clone().filter(|id| is_uuid(id)),
+            title: None,
+            content,
+            tags: None,
+            model: conversation.model.clone(),
+            created_at: timestamp(message.created_at.as_deref()).unwrap_or_else(|| created_at.clone()),
+            updated_at: None,
+        });
+    }
+
+    let name = conversation
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        .unwrap_or(UNTITLED_NAME)
+        .to_string();
+
+    let task = ExportTask {
+        uuid: conversation.uuid.filter(|id| is_uuid(id)),
+        name,
+        description: None,
+        updated_at: timestamp(conversation.updated_at.as_deref()),
+        created_at,
+        prompts,
+    };
+
+    (task, skipped)
+}
+
+/// 消息正文：优先取 `text`，为空时拼接文本块，附件和工具调用忽略
+fn message_text(message: &ChatMessage) -> Option<String> {
+    let text = match message.text.as_deref().map(str::trim) {
+        Some(text) if !text.is_empty() => text.to_string(),
+        _ => message
+            .content
+            .iter()
+            .filter(|block| block.kind == "text")
+            .filter_map(|block| block.text.as_deref())
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string(),
+    };
+
+    (!text.is_empty()).then_some(text)
+}
+
+fn timestamp(value: Option<&str>) -> Option<String> {
+    normalize_timestamp(value?).ok()
+}
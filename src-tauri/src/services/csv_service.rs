@@ -0,0 +1,257 @@
+use crate::error::AppError;
+use crate::models::{ConflictPolicy, CsvColumnMapping, CsvPreview, ImportFailure, ImportMode, ImportReport};
+use crate::services::backup_service::{self, ExportData, ExportPrompt, EXPORT_VERSION};
+use crate::services::markdown_service::{normalize_timestamp, task_entry, UNSORTED_NAME};
+use chrono::Utc;
+use csv::{ReaderBuilder, StringRecord, WriterBuilder};
+use rusqlite::Connection;
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+/// 导出文件的表头，也是导入时自动识别的标准列名
+pub const CSV_HEADERS: &[&str] = &[
+    "project",
+    "task",
+    "title",
+    "content",
+    "tags",
+    "model",
+    "created_at",
+    "updated_at",
+    "uuid",
+];
+
+/// 预览时返回的数据行数
+const PREVIEW_ROWS: usize = 5;
+/// UTF-8 BOM，便于 Excel 正确识别编码
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// 将全部提示词导出为 CSV，每行一条，返回写入的行数
+pub fn export_csv(conn: &Connection, path: &Path) -> Result<usize, AppError> {
+    let data = backup_service::collect_export_data(conn)?;
+
+    let mut writer = WriterBuilder::new().from_writer(UTF8_BOM.to_vec());
+    writer.write_record(CSV_HEADERS)?;
+
+    let mut written = 0;
+    for project in &data.projects {
+        for task in &project.tasks {
+            for prompt in &task.prompts {
+                let tags = prompt.tags.as_deref().unwrap_or_default().join(", ");
+                writer.write_record([
+                    project.name.as_str(),
+                    task.name.as_str(),
+                    prompt.title.as_deref().unwrap_or(""),
+                    prompt.content.as_str(),
+                    tags.as_str(),
+                    prompt.model.as_deref().unwrap_or(""),
+                    prompt.created_at.as_str(),
+                    prompt.updated_at.as_deref().unwrap_or(""),
+                    prompt.uuid.as_deref().unwrap_or(""),
+                ])?;
+                written += 1;
+            }
+        }
+    }
+
+    let bytes = writer.into_inner().map_err(|e| e.into_error())?;
+
+    // 先写入临时文件再替换，中途失败时不会破坏已有文件
+    let temp_path = path.with_extension("tmp");
+    let result = fs::write(&temp_path, &bytes).and_then(|_| fs::rename(&temp_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result?;
+
+    Ok(written)
+}
+
+/// 读取 CSV 表头和前几行，并按表头推测列映射，供用户确认后导入
+pub fn preview_csv(path: &Path) -> Result<CsvPreview, AppError> {
+    let mut reader = open_reader(path)?;
+    let headers: Vec<String> = reader.headers()?.iter().map(|h| h.trim().to_string()).collect();
+
+    let mut rows = Vec::new();
+    let mut total_rows = 0;
+    for record in reader.records() {
+        let record = record?;
+        if is_blank(&record) {
+            continue;
+        }
+        if rows.len() < PREVIEW_ROWS {
+            rows.push(record.iter().map(str::to_string).collect());
+        }
+        total_rows += 1;
+    }
+
+    Ok(CsvPreview {
+        suggested_mapping: suggest_mapping(&headers),
+        headers,
+        rows,
+        total_rows,
+    })
+}
+
+/// 按列映射导入 CSV
+///
+/// 内容为空或时间无法识别的行记入报告（路径为“第 N 行”），不中断导入。
+pub fn import_csv(
+    conn: &mut Connection,
+    path: &Path,
+    mapping: &CsvColumnMapping,
+    mode: ImportMode,
+    policy: ConflictPolicy,
+) -> Result<ImportReport, AppError> {
+    let content_column = mapping
+        .content
+        .ok_or_else(|| AppError::ValidationError("必须指定内容列".to_string()))?;
+
+    let mut reader = open_reader(path)?;
+    let column_count = reader.headers()?.len();
+    if content_column >= column_count {
+        return Err(AppError::ValidationError(format!(
+            "内容列 {} 超出范围，文件共 {} 列",
+            content_column + 1,
+            column_count
+        )));
+    }
+
+    let now = Utc::now().to_rfc3339();
+    let default_project = non_empty(mapping.default_project.as_deref()).unwrap_or(UNSORTED_NAME);
+    let default_task = non_empty(mapping.default_task.as_deref()).unwrap_or(UNSORTED_NAME);
+
+    let mut projects = Vec::new();
+    let mut failed_files = Vec::new();
+
+    for (index, record) in reader.records().enumerate() {
+        // 表头占第 1 行
+        let line = record
+            .as_ref()
+            .ok()
+            .and_then(|r| r.position())
+            .map(|p| p.line() as usize)
+            .unwrap_or(index + 2);
+
+        let result = record
+            .map_err(AppError::from)
+            .and_then(|record| {
+                if is_blank(&record) {
+                    return Ok(None);
+                }
+                read_row(&record, mapping, content_column, &now).map(|prompt| Some((record, prompt)))
+            });
+
+        match result {
+            Ok(Some((record, prompt))) => {
+                let project = non_empty(field(&record, mapping.project)).unwrap_or(default_project);
+                let task = non_empty(field(&record, mapping.task)).unwrap_or(default_task);
+
+                task_entry(&mut projects, project, task, &now).prompts.push(prompt);
+            }
+            Ok(None) => {}
+            Err(e) => failed_files.push(ImportFailure {
+                path: format!("第 {} 行", line),
+                reason: e.to_string(),
+            }),
+        }
+    }
+
+    let data = ExportData {
+        version: EXPORT_VERSION,
+        exported_at: now,
+        projects,
+    };
+
+    let mut report = backup_service::import_export_data(conn, data, mode, policy)?;
+    report.failed_files = failed_files;
+
+    Ok(report)
+}
+
+/// 打开 CSV 文件，去掉开头的 BOM
+fn open_reader(path: &Path) -> Result<csv::Reader<Box<dyn Read>>, AppError> {
+    let mut file = BufReader::new(File::open(path)?);
+
+    let mut bom = [0u8; 3];
+    let read = file.read(&mut bom)?;
+    let prefix = if bom[..read] == *UTF8_BOM { Vec::new() } else { bom[..read].to_vec() };
+    let source: Box<dyn Read> = Box::new(std::io::Cursor::new(prefix).chain(file));
+
+    Ok(ReaderBuilder::new().flexible(true).from_reader(source))
+}
+
+/// 将一行数据转换为导入记录
+fn read_row(
+    record: &StringRecord,
+    mapping: &CsvColumnMapping,
+    content_column: usize,
+    now: &str,
+) -> Result<ExportPrompt, AppError> {
+    let content = record.get(content_column).unwrap_or("").trim();
+    if content.is_empty() {
+        return Err(AppError::ValidationError("内容为空".to_string()));
+    }
+
+    let tags: Vec<String> = field(record, mapping.tags)
+        .unwrap_or("")
+        .split([',', '，', ';', '；'])
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    let created_at = match non_empty(field(record, mapping.created_at)) {
+        Some(value) => normalize_timestamp(value)?,
+        None => now.to_string(),
+    };
+    let updated_at = non_empty(field(record, mapping.updated_at))
+        .map(normalize_timestamp)
+        .transpose()?;
+
+    Ok(ExportPrompt {
+        uuid: non_empty(field(record, mapping.uuid)).map(str::to_string),
+        title: non_empty(field(record, mapping.title)).map(str::to_string),
+        content: content.to_string(),
+        tags: Some(tags).filter(|t| !t.is_empty()),
+        model: non_empty(field(record, mapping.model)).map(str::to_string),
+        created_at,
+        updated_at,
+    })
+}
+
+/// 按表头推测列映射，识别本应用导出的列名、常见中文列名以及 awesome-prompts 的 act / prompt 列
+fn suggest_mapping(headers: &[String]) -> CsvColumnMapping {
+    let find = |aliases: &[&str]| {
+        headers.iter().position(|h| {
+            let h = h.to_lowercase();
+            aliases.iter().any(|a| h == *a)
+        })
+    };
+
+    CsvColumnMapping {
+        project: find(&["project", "项目"]),
+        task: find(&["task", "任务"]),
+        title: find(&["title", "act", "name", "标题", "名称"]),
+        content: find(&["content", "prompt", "text", "内容", "提示词"]),
+        tags: find(&["tags", "tag", "标签"]),
+        model: find(&["model", "模型"]),
+        created_at: find(&["created_at", "created", "创建时间"]),
+        updated_at: find(&["updated_at", "updated", "更新时间"]),
+        uuid: find(&["uuid"]),
+        default_project: None,
+        default_task: None,
+    }
+}
+
+fn field(record: &StringRecord, column: Option<usize>) -> Option<&str> {
+    column.and_then(|c| record.get(c))
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|v| !v.is_empty())
+}
+
+fn is_blank(record: &StringRecord) -> bool {
+    record.iter().all(|v| v.trim().is_empty())
+}
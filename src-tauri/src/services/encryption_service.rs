@@ -0,0 +1,208 @@
+use crate::error::AppError;
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use sha2::{Digest, Sha256};
+
+/// 加密文件头标识
+const MAGIC: &[u8; 8] = b"PLOGENC\0";
+/// 当前信封格式版本
+const ENVELOPE_VERSION: u8 = 1;
+const KDF_ARGON2ID: u8 = 1;
+const CIPHER_XCHACHA20POLY1305: u8 = 1;
+
+/// Argon2id 默认参数：64 MiB 内存、3 轮、单线程
+const DEFAULT_MEMORY_KIB: u32 = 64 * 1024;
+const DEFAULT_ITERATIONS: u32 = 3;
+const DEFAULT_PARALLELISM: u32 = 1;
+/// 解密时接受的参数上限，防止恶意文件耗尽内存或长时间卡住
+const MAX_MEMORY_KIB: u32 = 1024 * 1024;
+const MAX_ITERATIONS: u32 = 64;
+const MAX_PARALLELISM: u32 = 16;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const KEY_CHECK_LEN: usize = 32;
+/// 文件头长度：标识 + 版本 + KDF 标识与参数 + 盐 + 算法标识 + nonce + 口令校验值
+const HEADER_LEN: usize = 8 + 1 + 1 + 12 + SALT_LEN + 1 + NONCE_LEN + KEY_CHECK_LEN;
+
+/// 内容是否为加密备份
+pub fn is_encrypted(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+/// 用口令加密
+///
+/// Argon2id 派生 64 字节：前半作为 XChaCha20-Poly1305 密钥，后半的哈希作为口令校验值，
+/// 用于区分口令错误和文件损坏。整个文件头作为附加数据参与认证。
+pub fn encrypt(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>, AppError> {
+    if passphrase.is_empty() {
+        return Err(AppError::ValidationError("口令不能为空".to_string()));
+    }
+
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let (key, key_check) =
+        derive_key(passphrase, &salt, DEFAULT_MEMORY_KIB, DEFAULT_ITERATIONS, DEFAULT_PARALLELISM)?;
+
+    let mut envelope = Vec::with_capacity(HEADER_LEN + plaintext.len() + 16);
+    envelope.extend_from_slice(MAGIC);
+    envelope.push(ENVELOPE_VERSION);
+    envelope.push(KDF_ARGON2ID);
+    envelope.extend_from_slice(&DEFAULT_MEMORY_KIB.to_le_bytes());
+    envelope.extend_from_slice(&DEFAULT_ITERATIONS.to_le_bytes());
+    envelope.extend_from_slice(&DEFAULT_PARALLELISM.to_le_bytes());
+    envelope.extend_from_slice(&salt);
+    envelope.push(CIPHER_XCHACHA20POLY1305);
+    envelope.extend_from_slice(&nonce);
+    envelope.extend_from_slice(&key_check);
+
+    let ciphertext = XChaCha20Poly1305::new(&key.into())
+        .encrypt(&nonce, Payload { msg: plaintext, aad: &envelope })
+        .map_err(|_| AppError::Unknown("加密失败".to_string()))?;
+    envelope.extend_from_slice(&ciphertext);
+
+    Ok(envelope)
+}
+
+/// 用口令解密
+///
+/// 口令校验值不符时返回 `WrongPassphrase`；校验值相符但认证失败说明文件被截断或篡改，返回 `BackupCorrupted`。
+pub fn decrypt(envelope: &[u8], passphrase: &str) -> Result<Vec<u8>, AppError> {
+    if !is_encrypted(envelope) {
+        return Err(AppError::ValidationError("不是加密备份文件".to_string()));
+    }
+    if envelope.len() < HEADER_LEN {
+        return Err(AppError::BackupCorrupted("加密文件头不完整".to_string()));
+    }
+
+    let mut reader = HeaderReader { bytes: envelope, offset: MAGIC.len() };
+    let version = reader.u8();
+    if version > ENVELOPE_VERSION {
+        return Err(AppError::ValidationError(format!(
+            "加密格式版本 {} 高于当前支持的 {}，请升级应用后再导入",
+            version, ENVELOPE_VERSION
+        )));
+    }
+    if reader.u8() != KDF_ARGON2ID {
+        return Err(AppError::BackupCorrupted("未知的密钥派生算法".to_string()));
+    }
+    let memory = reader.u32();
+    let iterations = reader.u32();
+    let parallelism = reader.u32();
+    if memory > MAX_MEMORY_KIB || iterations > MAX_ITERATIONS || parallelism > MAX_PARALLELISM {
+        return Err(AppError::BackupCorrupted("密钥派生参数异常".to_string()));
+    }
+    let salt = reader.take(SALT_LEN);
+    if reader.u8() != CIPHER_XCHACHA20POLY1305 {
+        return Err(AppError::BackupCorrupted("未知的加密算法".to_string()));
+    }
+    let nonce = XNonce::from_slice(reader.take(NONCE_LEN));
+    let stored_check = reader.take(KEY_CHECK_LEN);
+
+    let (key, key_check) = derive_key(passphrase, salt, memory, iterations, parallelism)?;
+    if key_check.as_slice() != stored_check {
+        return Err(AppError::WrongPassphrase);
+    }
+
+    let (header, ciphertext) = envelope.split_at(HEADER_LEN);
+    XChaCha20Poly1305::new(&key.into())
+        .decrypt(nonce, Payload { msg: ciphertext, aad: header })
+        .map_err(|_| AppError::BackupCorrupted("加密内容校验失败".to_string()))
+}
+
+/// 派生加密密钥和口令校验值
+fn derive_key(
+    passphrase: &str,
+    salt: &[u8],
+    memory: u32,
+    iterations: u32,
+    parallelism: u32,
+) -> Result<([u8; 32], [u8; KEY_CHECK_LEN]), AppError> {
+    let params = Params::new(memory, iterations, parallelism, Some(64))
+        .map_err(|e| AppError::BackupCorrupted(format!("密钥派生参数异常: {}", e)))?;
+
+    let mut output = [0u8; 64];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut output)
+        .map_err(|e| AppError::Unknown(format!("密钥派生失败: {}", e)))?;
+
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&output[..32]);
+
+    let key_check: [u8; KEY_CHECK_LEN] = Sha256::digest(&output[32..]).into();
+
+    Ok((key, key_check))
+}
+
+/// 按顺序读取定长文件头，调用前已确认长度足够
+struct HeaderReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> HeaderReader<'a> {
+    fn take(&mut self, len: usize) -> &'a [u8] {
+        let slice = &self.bytes[self.offset..self.offset + len];
+        self.offset += len;
+        slice
+    }
+
+    fn u8(&mut self) -> u8 {
+        self.take(1)[0]
+    }
+
+    fn u32(&mut self) -> u32 {
+        let mut buf = [0u8; 4];
+        buf.copy_from_slice(self.take(4));
+        u32::from_le_bytes(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ApiError;
+
+    const PLAINTEXT: &[u8] = b"{\"version\":2,\"projects\":[]}";
+
+    fn error_code(result: Result<Vec<u8>, AppError>) -> String {
+        ApiError::from(result.unwrap_err()).code
+    }
+
+    #[test]
+    fn roundtrip() {
+        let envelope = encrypt(PLAINTEXT, "口令 passphrase").unwrap();
+
+        assert!(is_encrypted(&envelope));
+        assert_ne!(&envelope[HEADER_LEN..], PLAINTEXT);
+        assert_eq!(decrypt(&envelope, "口令 passphrase").unwrap(), PLAINTEXT);
+    }
+
+    #[test]
+    fn wrong_passphrase() {
+        let envelope = encrypt(PLAINTEXT, "right").unwrap();
+
+        assert_eq!(error_code(decrypt(&envelope, "wrong")), "WRONG_PASSPHRASE");
+    }
+
+    #[test]
+    fn flipped_ciphertext_byte_is_corrupted() {
+        let mut envelope = encrypt(PLAINTEXT, "right").unwrap();
+        envelope[HEADER_LEN + 3] ^= 0x01;
+
+        assert_eq!(error_code(decrypt(&envelope, "right")), "BACKUP_CORRUPTED");
+    }
+
+    #[test]
+    fn truncated_file_is_corrupted() {
+        let envelope = encrypt(PLAINTEXT, "right").unwrap();
+
+        // 截断在密文中间和文件头中间
+        assert_eq!(error_code(decrypt(&envelope[..envelope.len() - 1], "right")), "BACKUP_CORRUPTED");
+        assert_eq!(error_code(decrypt(&envelope[..HEADER_LEN - 1], "right")), "BACKUP_CORRUPTED");
+    }
+}
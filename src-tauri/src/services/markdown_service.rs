@@ -0,0 +1,348 @@
+use crate::error::AppError;
+use crate::models::{ConflictPolicy, ImportFailure, ImportMode, ImportReport, PromptEntryDto};
+use crate::services::backup_service::{
+    self, ExportData, ExportProject, ExportPrompt, ExportTask, EXPORT_VERSION,
+};
+use crate::utils::file_name::{sanitize_file_name, unique_name};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use rusqlite::Connection;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use walkdir::{DirEntry, WalkDir};
+
+/// 目录导入时识别的文件扩展名
+const IMPORT_EXTENSIONS: &[&str] = &["md", "markdown", "txt"];
+/// 不在项目或任务目录下的文件归入的名称
+pub const UNSORTED_NAME: &str = "未分类";
+
+/// Markdown 文件头部的 YAML 元数据
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PromptFrontMatter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// 读取时也接受逗号分隔的字符串
+    #[serde(default, skip_serializing_if = "Vec::is_empty", deserialize_with = "deserialize_tags")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none", alias = "created")]
+    pub created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none", alias = "updated")]
+    pub updated_at: Option<String>,
+}
+
+fn deserialize_tags<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Tags {
+        List(Vec<String>),
+        Text(String),
+    }
+
+    let tags = match Option::<Tags>::deserialize(deserializer)? {
+        Some(Tags::List(list)) => list,
+        Some(Tags::Text(text)) => text.split([',', '，']).map(|t| t.to_string()).collect(),
+        None => Vec::new(),
+    };
+
+    Ok(tags
+        .into_iter()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect())
+}
+
+impl From<&ExportPrompt> for PromptFrontMatter {
+    fn from(prompt: &ExportPrompt) -> Self {
+        PromptFrontMatter {
+            uuid: prompt.uuid.clone(),
+            title: prompt.title.clone(),
+            tags: prompt.tags.clone().unwrap_or_default(),
+            model: prompt.model.clone(),
+            created_at: Some(prompt.created_at.clone()),
+            updated_at: prompt.updated_at.clone(),
+        }
+    }
+}
+
+impl From<&PromptEntryDto> for PromptFrontMatter {
+    fn from(prompt: &PromptEntryDto) -> Self {
+        PromptFrontMatter {
+            uuid: Some(prompt.uuid.clone()),
+            title: prompt.title.clone(),
+            tags: prompt.tags.clone().unwrap_or_default(),
+            model: prompt.model.clone(),
+            created_at: Some(prompt.created_at.clone()),
+            updated_at: prompt.updated_at.clone(),
+        }
+    }
+}
+
+/// 生成带 YAML 头的 Markdown 文本，正文即提示词内容
+pub fn render_markdown(front: &PromptFrontMatter, content: &str) -> Result<String, AppError> {
+    let yaml = serde_yaml::to_string(front)?;
+
+    Ok(format!("---\n{}---\n\n{}\n", yaml, content))
+}
+
+/// 解析 Markdown 文本，返回 YAML 头和正文；没有 YAML 头时整个文本即正文
+pub fn parse_markdown(text: &str) -> Result<(PromptFrontMatter, String), AppError> {
+    let text = text.trim_start_matches('\u{feff}');
+
+    let Some(rest) = text
+        .strip_prefix("---\n")
+        .or_else(|| text.strip_prefix("---\r\n"))
+    else {
+        return Ok((PromptFrontMatter::default(), text.trim_end().to_string()));
+    };
+
+    // 查找结束分隔线
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if matches!(line.trim_end(), "---" | "...") {
+            let yaml = &rest[..offset];
+            let body = &rest[offset + line.len()..];
+
+            let front = if yaml.trim().is_empty() {
+                PromptFrontMatter::default()
+            } else {
+                serde_yaml::from_str(yaml)?
+            };
+            let body = body.trim_start_matches(['\r', '\n']).trim_end();
+
+            return Ok((front, body.to_string()));
+        }
+        offset += line.len();
+    }
+
+    Err(AppError::ValidationError("YAML 头缺少结束分隔线 ---".to_string()))
+}
+
+/// 将全部数据导出为 Markdown 目录：`项目/任务/NNN-标题.md`，返回写入的文件数
+pub fn export_vault(conn: &Connection, dir: &Path) -> Result<usize, AppError> {
+    let data = backup_service::collect_export_data(conn)?;
+    fs::create_dir_all(dir)?;
+
+    let mut written = 0;
+    let mut project_names = HashSet::new();
+
+    for project in &data.projects {
+        let project_dir = dir.join(unique_name(
+            sanitize_file_name(&project.name, "未命名项目"),
+            &mut project_names,
+        ));
+        fs::create_dir_all(&project_dir)?;
+
+        let mut task_names = HashSet::new();
+        for task in &project.tasks {
+            let task_dir = project_dir.join(unique_name(
+                sanitize_file_name(&task.name, "未命名任务"),
+                &mut task_names,
+            ));
+            fs::create_dir_all(&task_dir)?;
+
+            for (index, prompt) in task.prompts.iter().enumerate() {
+                let title = sanitize_file_name(prompt.title.as_deref().unwrap_or(""), "未命名");
+                let file_name = format!("{:03}-{}.md", index + 1, title);
+
+                let markdown = render_markdown(&PromptFrontMatter::from(prompt), &prompt.content)?;
+                fs::write(task_dir.join(file_name), markdown)?;
+                written += 1;
+            }
+        }
+    }
+
+    Ok(written)
+}
+
+/// 从目录导入 Markdown / 文本文件
+///
+/// 第一级目录对应项目，第二级目录对应任务，更深层的文件归入所在的第二级任务；
+/// 不在项目或任务目录下的文件归入“未分类”。无法解析的文件记入报告，不中断导入。
+pub fn import_folder(
+    conn: &mut Connection,
+    dir: &Path,
+    mode: ImportMode,
+    policy: ConflictPolicy,
+) -> Result<ImportReport, AppError> {
+    if !dir.is_dir() {
+        return Err(AppError::ValidationError(format!("{} 不是目录", dir.display())));
+    }
+
+    let now = Utc::now().to_rfc3339();
+    let mut projects: Vec<ExportProject> = Vec::new();
+    let mut failed_files = Vec::new();
+
+    let walker = WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !is_hidden(e));
+
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                failed_files.push(ImportFailure {
+                    path: e.path().map(|p| relative_path(dir, p)).unwrap_or_default(),
+                    reason: e.to_string(),
+                });
+                continue;
+            }
+        };
+
+        if !entry.file_type().is_file() || !is_importable(entry.path()) {
+            continue;
+        }
+
+        let relative = entry.path().strip_prefix(dir).unwrap_or(entry.path());
+        match read_prompt_file(entry.path(), &now) {
+            Ok(prompt) => {
+                let folders: Vec<String> = relative
+                    .parent()
+                    .map(|p| p.iter().map(|c| c.to_string_lossy().to_string()).collect())
+                    .unwrap_or_default();
+                let project = folders.first().map(String::as_str).unwrap_or(UNSORTED_NAME);
+                let task = folders.get(1).map(String::as_str).unwrap_or(UNSORTED_NAME);
+
+                task_entry(&mut projects, project, task, &now).prompts.push(prompt);
+            }
+            Err(e) => failed_files.push(ImportFailure {
+                path: relative_path(dir, entry.path()),
+                reason: e.to_string(),
+            }),
+        }
+    }
+
+    let data = ExportData {
+        version: EXPORT_VERSION,
+        exported_at: now,
+        projects,
+    };
+
+    let mut report = backup_service::import_export_data(conn, data, mode, policy)?;
+    report.failed_files = failed_files;
+
+    Ok(report)
+}
+
+/// 读取单个文件并转换为导入记录
+fn read_prompt_file(path: &Path, now: &str) -> Result<ExportPrompt, AppError> {
+    let text = fs::read_to_string(path)?;
+    let (front, content) = parse_markdown(&text)?;
+
+    if content.trim().is_empty() {
+        return Err(AppError::ValidationError("文件内容为空".to_string()));
+    }
+
+    // 没有标题时使用文件名，并去掉导出时添加的序号前缀
+    let title = front.title.filter(|t| !t.trim().is_empty()).or_else(|| {
+        let stem = path.file_stem()?.to_string_lossy().to_string();
+        let stripped = stem
+            .split_once('-')
+            .filter(|(prefix, _)| !prefix.is_empty() && prefix.chars().all(|c| c.is_ascii_digit()))
+            .map(|(_, rest)| rest.to_string())
+            .unwrap_or(stem);
+        Some(stripped).filter(|t| !t.trim().is_empty())
+    });
+
+    let created_at = match front.created_at {
+        Some(value) => normalize_timestamp(&value)?,
+        None => fs::metadata(path)
+            .and_then(|m| m.modified())
+            .map(|t| DateTime::<Utc>::from(t).to_rfc3339())
+            .unwrap_or_else(|_| now.to_string()),
+    };
+    let updated_at = front.updated_at.as_deref().map(normalize_timestamp).transpose()?;
+
+    Ok(ExportPrompt {
+        uuid: front.uuid,
+        title,
+        content,
+        tags: Some(front.tags).filter(|t| !t.is_empty()),
+        model: front.model,
+        created_at,
+        updated_at,
+    })
+}
+
+/// 将导入文件中的时间统一为 RFC 3339，未带时区的按 UTC 处理
+pub fn normalize_timestamp(value: &str) -> Result<String, AppError> {
+    let value = value.trim();
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(dt.to_rfc3339());
+    }
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(value, format) {
+            return Ok(dt.and_utc().to_rfc3339());
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc().to_rfc3339());
+    }
+
+    Err(AppError::ValidationError(format!("无法识别的时间: {}", value)))
+}
+
+/// 在导入结构中查找或创建指定名称的项目和任务
+pub fn task_entry<'a>(
+    projects: &'a mut Vec<ExportProject>,
+    project_name: &str,
+    task_name: &str,
+    now: &str,
+) -> &'a mut ExportTask {
+    let project_index = match projects.iter().position(|p| p.name == project_name) {
+        Some(index) => index,
+        None => {
+            projects.push(ExportProject {
+                uuid: None,
+                name: project_name.to_string(),
+                created_at: now.to_string(),
+                updated_at: None,
+                tasks: Vec::new(),
+            });
+            projects.len() - 1
+        }
+    };
+
+    let tasks = &mut projects[project_index].tasks;
+    let task_index = match tasks.iter().position(|t| t.name == task_name) {
+        Some(index) => index,
+        None => {
+            tasks.push(ExportTask {
+                uuid: None,
+                name: task_name.to_string(),
+                description: None,
+                created_at: now.to_string(),
+                updated_at: None,
+                prompts: Vec::new(),
+            });
+            tasks.len() - 1
+        }
+    };
+
+    &mut tasks[task_index]
+}
+
+/// 跳过隐藏文件和目录，如 .git、.obsidian
+pub fn is_hidden(entry: &DirEntry) -> bool {
+    entry.file_name().to_string_lossy().starts_with('.')
+}
+
+/// 是否为可导入的 Markdown / 文本文件
+pub fn is_importable(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .is_some_and(|ext| IMPORT_EXTENSIONS.contains(&ext.as_str()))
+}
+
+fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root).unwrap_or(path).to_string_lossy().to_string()
+}
@@ -1,3 +1,17 @@
 // Service 层 - 业务逻辑
 pub mod search_service;
+pub mod archive_service;
+pub mod auto_backup_service;
 pub mod backup_service;
+pub mod chat_export_service;
+pub mod chatgpt_service;
+pub mod claude_service;
+pub mod csv_service;
+pub mod encryption_service;
+pub mod markdown_service;
+pub mod preview_service;
+pub mod schema_service;
+pub mod site_service;
+pub mod snapshot_service;
+pub mod sync_service;
+pub mod trash_service;
@@ -0,0 +1,246 @@
+use crate::error::AppError;
+use crate::models::{
+    DiffChange, DiffCounts, DiffItem, ImportDiff, ImportPreview, ProjectPreview, TaskPreview,
+};
+use crate::services::backup_service::{self, ExportData, ExportPrompt, ExportTask};
+use rusqlite::Connection;
+use std::collections::HashMap;
+
+/// 差异明细最多返回的条数
+const MAX_DIFF_ITEMS: usize = 200;
+
+/// 生成导入预览：统计备份内容，并与当前数据比对
+///
+/// 项目、任务、提示词的匹配方式与合并导入一致：优先按 uuid，其次按名称（提示词按所在任务和内容）。
+pub fn preview_import(conn: &Connection, data: &ExportData) -> Result<ImportPreview, AppError> {
+    let current = backup_service::collect_export_data(conn)?;
+
+    let projects: Vec<ProjectPreview> = data
+        .projects
+        .iter()
+        .map(|project| {
+            let tasks: Vec<TaskPreview> = project
+                .tasks
+                .iter()
+                .map(|task| TaskPreview {
+                    name: task.name.clone(),
+                    prompt_count: task.prompts.len(),
+                })
+                .collect();
+
+            ProjectPreview {
+                name: project.name.clone(),
+                prompt_count: tasks.iter().map(|t| t.prompt_count).sum(),
+                tasks,
+            }
+        })
+        .collect();
+
+    let prompts = data
+        .projects
+        .iter()
+        .flat_map(|p| &p.tasks)
+        .flat_map(|t| &t.prompts);
+    let earliest_at = prompts.clone().map(|p| p.created_at.as_str()).min().map(str::to_string);
+    let latest_at = prompts.clone().map(|p| p.created_at.as_str()).max().map(str::to_string);
+
+    Ok(ImportPreview {
+        version: data.version,
+        exported_at: data.exported_at.clone(),
+        project_count: projects.len(),
+        task_count: projects.iter().map(|p| p.tasks.len()).sum(),
+        prompt_count: prompts.count(),
+        earliest_at,
+        latest_at,
+        projects,
+        diff: diff(&current, data),
+    })
+}
+
+/// 当前数据的扁平索引，记录每个条目是否已被备份中的条目匹配
+struct Library<'a> {
+    projects: Vec<ProjectRef<'a>>,
+    tasks: Vec<TaskRef<'a>>,
+    prompts: Vec<PromptRef<'a>>,
+    project_by_uuid: HashMap<&'a str, usize>,
+    project_by_name: HashMap<&'a str, usize>,
+    task_by_uuid: HashMap<&'a str, usize>,
+    task_by_name: HashMap<(usize, &'a str), usize>,
+    prompt_by_uuid: HashMap<&'a str, usize>,
+    prompt_by_content: HashMap<(usize, &'a str), usize>,
+}
+
+struct ProjectRef<'a> {
+    name: &'a str,
+    matched: bool,
+}
+
+struct TaskRef<'a> {
+    project: usize,
+    task: &'a ExportTask,
+    matched: bool,
+}
+
+struct PromptRef<'a> {
+    task: usize,
+    prompt: &'a ExportPrompt,
+    matched: bool,
+}
+
+impl<'a> Library<'a> {
+    fn new(data: &'a ExportData) -> Self {
+        let mut library = Library {
+            projects: Vec::new(),
+            tasks: Vec::new(),
+            prompts: Vec::new(),
+            project_by_uuid: HashMap::new(),
+            project_by_name: HashMap::new(),
+            task_by_uuid: HashMap::new(),
+            task_by_name: HashMap::new(),
+            prompt_by_uuid: HashMap::new(),
+            prompt_by_content: HashMap::new(),
+        };
+
+        for project in &data.projects {
+            let pi = library.projects.len();
+            library.projects.push(ProjectRef { name: &project.name, matched: false });
+            if let Some(uuid) = &project.uuid {
+                library.project_by_uuid.insert(uuid, pi);
+            }
+            library.project_by_name.entry(&project.name).or_insert(pi);
+
+            for task in &project.tasks {
+                let ti = library.tasks.len();
+                library.tasks.push(TaskRef { project: pi, task, matched: false });
+                if let Some(uuid) = &task.uuid {
+                    library.task_by_uuid.insert(uuid, ti);
+                }
+                library.task_by_name.entry((pi, &task.name)).or_insert(ti);
+
+                for prompt in &task.prompts {
+                    let index = library.prompts.len();
+                    library.prompts.push(PromptRef { task: ti, prompt, matched: false });
+                    if let Some(uuid) = &prompt.uuid {
+                        library.prompt_by_uuid.insert(uuid, index);
+                    }
+                    library.prompt_by_content.entry((ti, &prompt.content)).or_insert(index);
+                }
+            }
+        }
+
+        library
+    }
+
+    fn match_project(&mut self, uuid: Option<&str>, name: &str) -> Option<usize> {
+        let index = uuid
+            .and_then(|u| self.project_by_uuid.get(u))
+            .filter(|&&i| !self.projects[i].matched)
+            .or_else(|| self.project_by_name.get(name).filter(|&&i| !self.projects[i].matched))
+            .copied()?;
+        self.projects[index].matched = true;
+        Some(index)
+    }
+
+    fn match_task(&mut self, uuid: Option<&str>, project: Option<usize>, name: &str) -> Option<usize> {
+        let by_name = project.and_then(|p| self.task_by_name.get(&(p, name)));
+        let index = uuid
+            .and_then(|u| self.task_by_uuid.get(u))
+            .filter(|&&i| !self.tasks[i].matched)
+            .or_else(|| by_name.filter(|&&i| !self.tasks[i].matched))
+            .copied()?;
+        self.tasks[index].matched = true;
+        Some(index)
+    }
+
+    fn match_prompt(&mut self, uuid: Option<&str>, task: Option<usize>, content: &str) -> Option<usize> {
+        let by_content = task.and_then(|t| self.prompt_by_content.get(&(t, content)));
+        let index = uuid
+            .and_then(|u| self.prompt_by_uuid.get(u))
+            .filter(|&&i| !self.prompts[i].matched)
+            .or_else(|| by_content.filter(|&&i| !self.prompts[i].matched))
+            .copied()?;
+        self.prompts[index].matched = true;
+        Some(index)
+    }
+}
+
+/// 比对当前数据和备份数据
+fn diff(current: &ExportData, incoming: &ExportData) -> ImportDiff {
+    let mut library = Library::new(current);
+    let mut result = ImportDiff::default();
+
+    for project in &incoming.projects {
+        let matched_project = library.match_project(project.uuid.as_deref(), &project.name);
+        count(
+            &mut result.projects,
+            matched_project.map(|i| library.projects[i].name != project.name),
+        );
+
+        for task in &project.tasks {
+            let matched_task = library.match_task(task.uuid.as_deref(), matched_project, &task.name);
+            count(
+                &mut result.tasks,
+                matched_task.map(|i| {
+                    let current = library.tasks[i].task;
+                    current.name != task.name || current.description != task.description
+                }),
+            );
+
+            for prompt in &task.prompts {
+                let matched = library.match_prompt(prompt.uuid.as_deref(), matched_task, &prompt.content);
+                let changed = matched.map(|i| !same_prompt(library.prompts[i].prompt, prompt));
+                count(&mut result.prompts, changed);
+
+                let change = match changed {
+                    None => Some(DiffChange::Added),
+                    Some(true) => Some(DiffChange::Changed),
+                    Some(false) => None,
+                };
+                if let Some(change) = change {
+                    push_item(&mut result.items, change, &project.name, &task.name, prompt);
+                }
+            }
+        }
+    }
+
+    // 剩余未匹配的即为备份中没有的条目
+    result.projects.removed = library.projects.iter().filter(|p| !p.matched).count();
+    result.tasks.removed = library.tasks.iter().filter(|t| !t.matched).count();
+    for prompt in library.prompts.iter().filter(|p| !p.matched) {
+        result.prompts.removed += 1;
+
+        let task = &library.tasks[prompt.task];
+        let project = library.projects[task.project].name;
+        push_item(&mut result.items, DiffChange::Removed, project, &task.task.name, prompt.prompt);
+    }
+
+    result
+}
+
+/// 按匹配结果计数：None 为新增，Some(true) 为有变化，Some(false) 为相同
+fn count(counts: &mut DiffCounts, changed: Option<bool>) {
+    match changed {
+        None => counts.added += 1,
+        Some(true) => counts.changed += 1,
+        Some(false) => counts.unchanged += 1,
+    }
+}
+
+fn same_prompt(a: &ExportPrompt, b: &ExportPrompt) -> bool {
+    a.content == b.content
+        && a.title == b.title
+        && a.model == b.model
+        && a.tags.as_deref().unwrap_or_default() == b.tags.as_deref().unwrap_or_default()
+}
+
+fn push_item(items: &mut Vec<DiffItem>, change: DiffChange, project: &str, task: &str, prompt: &ExportPrompt) {
+    if items.len() < MAX_DIFF_ITEMS {
+        items.push(DiffItem {
+            change,
+            project: project.to_string(),
+            task: task.to_string(),
+            title: prompt.title.clone(),
+            uuid: prompt.uuid.clone(),
+        });
+    }
+}
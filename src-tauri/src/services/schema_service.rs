@@ -0,0 +1,98 @@
+use crate::error::AppError;
+use crate::services::backup_service::{ExportData, EXPORT_VERSION};
+use serde_json::Value;
+
+/// 当前导出格式的 JSON Schema
+pub const EXPORT_SCHEMA: &str = include_str!("../../schemas/export.schema.json");
+
+/// 旧版本文件没有 version 字段时按版本 1 处理
+const INITIAL_VERSION: i32 = 1;
+
+/// 将上一版本的数据原地升级到下一版本
+type Upgrade = fn(&mut Value) -> Result<(), AppError>;
+
+/// 升级步骤：`UPGRADES[i]` 将版本 i + 1 的数据升级到版本 i + 2
+const UPGRADES: &[Upgrade] = &[upgrade_v1_to_v2];
+
+/// 解析导出文件：检查版本、逐级升级到当前版本，再反序列化并校验
+///
+/// 高于当前支持版本的文件直接拒绝；字段类型或取值错误时，错误信息中带有出错位置，如 `$.projects[0].tasks[2].name`。
+pub fn parse_export_data(bytes: &[u8]) -> Result<ExportData, AppError> {
+    let mut value: Value = serde_json::from_slice(bytes)?;
+
+    let version = read_version(&value)?;
+    if version > EXPORT_VERSION {
+        return Err(AppError::ValidationError(format!(
+            "备份文件格式版本 {} 高于当前支持的 {}，请升级应用后再导入",
+            version, EXPORT_VERSION
+        )));
+    }
+
+    for upgrade in &UPGRADES[(version - INITIAL_VERSION) as usize..] {
+        upgrade(&mut value)?;
+    }
+
+    let data: ExportData = serde_path_to_error::deserialize(value).map_err(|e| {
+        AppError::ValidationError(format!("{}: {}", json_path(&e.path().to_string()), e.inner()))
+    })?;
+    validate(&data)?;
+
+    Ok(data)
+}
+
+fn read_version(value: &Value) -> Result<i32, AppError> {
+    let object = value
+        .as_object()
+        .ok_or_else(|| AppError::ValidationError("$: 顶层必须是对象".to_string()))?;
+
+    let Some(version) = object.get("version") else {
+        return Ok(INITIAL_VERSION);
+    };
+
+    version
+        .as_i64()
+        .and_then(|v| i32::try_from(v).ok())
+        .filter(|v| *v >= INITIAL_VERSION)
+        .ok_or_else(|| AppError::ValidationError(format!("$.version: 无效的版本号 {}", version)))
+}
+
+/// 版本 1 → 2：增加可选的 uuid 字段，已有数据无需改动
+fn upgrade_v1_to_v2(value: &mut Value) -> Result<(), AppError> {
+    value["version"] = Value::from(2);
+    Ok(())
+}
+
+/// 反序列化之后的取值校验
+fn validate(data: &ExportData) -> Result<(), AppError> {
+    let invalid = |path: String, message: &str| AppError::ValidationError(format!("{}: {}", path, message));
+
+    for (pi, project) in data.projects.iter().enumerate() {
+        let path = format!("$.projects[{}]", pi);
+        if project.name.trim().is_empty() {
+            return Err(invalid(format!("{}.name", path), "项目名称不能为空"));
+        }
+
+        for (ti, task) in project.tasks.iter().enumerate() {
+            let path = format!("{}.tasks[{}]", path, ti);
+            if task.name.trim().is_empty() {
+                return Err(invalid(format!("{}.name", path), "任务名称不能为空"));
+            }
+
+            for (ri, prompt) in task.prompts.iter().enumerate() {
+                if prompt.content.trim().is_empty() {
+                    return Err(invalid(format!("{}.prompts[{}].content", path, ri), "提示词内容不能为空"));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// serde_path_to_error 的路径形如 `projects[0].name`，统一加上 `$.` 前缀
+fn json_path(path: &str) -> String {
+    match path {
+        "" | "." => "$".to_string(),
+        path => format!("$.{}", path),
+    }
+}
@@ -1,8 +1,13 @@
 use crate::error::AppError;
-use crate::models::SearchResultDto;
-use rusqlite::Connection;
+use crate::models::{QuickSwitchItemDto, SearchResultDto};
+use crate::utils::pinyin::{find_pinyin_match, is_pinyin_query};
+use rusqlite::{params, Connection};
 
 /// 搜索提示词记录
+///
+/// 索引使用 trigram 分词，可直接按子串匹配中文；长度不少于 3 个字符的词走
+/// FTS5 MATCH 并按 bm25 相关度排序，更短的词（如两个汉字）用 LIKE 补充过滤。
+/// 单个纯字母查询词还会按标题拼音（全拼或首字母）匹配，结果追加在全文结果之后。
 pub fn search_prompts(
     conn: &Connection,
     keyword: &str,
@@ -10,15 +15,18 @@ pub fn search_prompts(
     task_id: Option<i64>,
     limit: i64,
 ) -> Result<Vec<SearchResultDto>, AppError> {
-    if keyword.trim().is_empty() {
+    let terms = split_terms(keyword);
+    if terms.is_empty() {
         return Ok(vec![]);
     }
 
-    let search_pattern = format!("%{}%", keyword);
+    let (match_terms, like_terms): (Vec<&String>, Vec<&String>) = terms
+        .iter()
+        .partition(|t| t.chars().count() >= TRIGRAM_MIN_CHARS);
 
     let mut sql = String::from(
         r#"
-        SELECT 
+        SELECT
             p.id as project_id,
             t.id as task_id,
             pe.id as prompt_id,
@@ -30,47 +38,306 @@ pub fn search_prompts(
         FROM prompt_entries pe
         JOIN tasks t ON pe.task_id = t.id
         JOIN projects p ON t.project_id = p.id
-        WHERE (pe.title LIKE ? OR pe.content LIKE ?)
         "#,
     );
 
-    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![
-        Box::new(search_pattern.clone()),
-        Box::new(search_pattern.clone()),
-    ];
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
 
-    if let Some(pid) = project_id {
-        sql.push_str(" AND p.id = ?");
-        params_vec.push(Box::new(pid));
+    if match_terms.is_empty() {
+        sql.push_str(" WHERE 1 = 1");
+    } else {
+        sql.push_str(
+            " JOIN prompt_entries_fts ON prompt_entries_fts.rowid = pe.id
+              WHERE prompt_entries_fts MATCH ?",
+        );
+        params_vec.push(Box::new(build_match_query(&match_terms)));
     }
-    if let Some(tid) = task_id {
-        sql.push_str(" AND t.id = ?");
-        params_vec.push(Box::new(tid));
+    sql.push_str(NOT_TRASHED);
+
+    for term in &like_terms {
+        let pattern = format!("%{}%", escape_like(term));
+        sql.push_str(" AND (pe.title LIKE ? ESCAPE '\\' OR pe.content LIKE ? ESCAPE '\\')");
+        params_vec.push(Box::new(pattern.clone()));
+        params_vec.push(Box::new(pattern));
     }
 
-    sql.push_str(" ORDER BY pe.created_at DESC LIMIT ?");
+    push_scope_filters(&mut sql, &mut params_vec, project_id, task_id);
+
+    if match_terms.is_empty() {
+        sql.push_str(" ORDER BY pe.created_at DESC LIMIT ?");
+    } else {
+        // 标题命中的权重高于内容命中；相关度相同时新记录优先
+        sql.push_str(" ORDER BY bm25(prompt_entries_fts, ?, ?), pe.created_at DESC LIMIT ?");
+        params_vec.push(Box::new(TITLE_WEIGHT));
+        params_vec.push(Box::new(CONTENT_WEIGHT));
+    }
     params_vec.push(Box::new(limit));
 
     let mut stmt = conn.prepare(&sql)?;
     let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
 
-    let keyword_clone = keyword.to_string();
-    let results: Vec<SearchResultDto> = stmt
+    let mut results: Vec<SearchResultDto> = stmt
+        .query_map(params_refs.as_slice(), |row| parse_search_row(row, keyword, &terms))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if let [term] = terms.as_slice() {
+        if is_pinyin_query(term) && (results.len() as i64) < limit {
+            let remaining = limit - results.len() as i64;
+            let extra = search_by_title_pinyin(conn, term, project_id, task_id, remaining, &results)?;
+            results.extend(extra);
+        }
+    }
+
+    Ok(results)
+}
+
+/// 按标题拼音搜索提示词，跳过已在 `existing` 中的记录
+fn search_by_title_pinyin(
+    conn: &Connection,
+    query: &str,
+    project_id: Option<i64>,
+    task_id: Option<i64>,
+    limit: i64,
+    existing: &[SearchResultDto],
+) -> Result<Vec<SearchResultDto>, AppError> {
+    let pattern = format!("%{}%", query.to_lowercase());
+
+    let mut sql = String::from(
+        r#"
+        SELECT
+            p.id as project_id,
+            t.id as task_id,
+            pe.id as prompt_id,
+            p.name as project_name,
+            t.name as task_name,
+            pe.title,
+            pe.created_at
+        FROM prompt_entries pe
+        JOIN tasks t ON pe.task_id = t.id
+        JOIN projects p ON t.project_id = p.id
+        WHERE (pe.title_initials LIKE ? OR pe.title_pinyin LIKE ?)
+        "#,
+    );
+    sql.push_str(NOT_TRASHED);
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> =
+        vec![Box::new(pattern.clone()), Box::new(pattern)];
+
+    push_scope_filters(&mut sql, &mut params_vec, project_id, task_id);
+
+    // 多取一些，以便过滤掉已命中的记录后仍能填满 limit
+    sql.push_str(" ORDER BY pe.created_at DESC LIMIT ?");
+    params_vec.push(Box::new(limit + existing.len() as i64));
+
+    let mut stmt = conn.prepare(&sql)?;
+    let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
+
+    let results = stmt
         .query_map(params_refs.as_slice(), |row| {
-            parse_search_row(row, &keyword_clone)
+            let title: String = row.get::<_, Option<String>>(5)?.unwrap_or_default();
+            let highlight = find_pinyin_match(&title, query).map(|(s, e)| title[s..e].to_string());
+
+            Ok(SearchResultDto {
+                project_id: row.get(0)?,
+                task_id: row.get(1)?,
+                prompt_id: row.get(2)?,
+                project_name: row.get(3)?,
+                task_name: row.get(4)?,
+                snippet: format!("[标题] {}", title),
+                highlight,
+                created_at: row.get(6)?,
+            })
         })?
-        .collect::<Result<Vec<_>, _>>()?;
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .filter(|r| !existing.iter().any(|e| e.prompt_id == r.prompt_id))
+        .take(limit as usize)
+        .collect();
 
     Ok(results)
 }
 
-fn parse_search_row(row: &rusqlite::Row, keyword: &str) -> rusqlite::Result<SearchResultDto> {
+/// 排除回收站中的记录（提示词本身或其所属任务、项目已删除）
+const NOT_TRASHED: &str =
+    " AND pe.deleted_at IS NULL AND t.deleted_at IS NULL AND p.deleted_at IS NULL";
+
+/// 追加项目 / 任务范围过滤条件
+fn push_scope_filters(
+    sql: &mut String,
+    params_vec: &mut Vec<Box<dyn rusqlite::ToSql>>,
+    project_id: Option<i64>,
+    task_id: Option<i64>,
+) {
+    if let Some(pid) = project_id {
+        sql.push_str(" AND p.id = ?");
+        params_vec.push(Box::new(pid));
+    }
+    if let Some(tid) = task_id {
+        sql.push_str(" AND t.id = ?");
+        params_vec.push(Box::new(tid));
+    }
+}
+
+/// 快速切换：按名称、全拼或拼音首字母查找项目、任务和带标题的提示词
+///
+/// 名称以查询词开头的条目排在前面；同等情况下按项目、任务、提示词的顺序排列。
+pub fn quick_switch(
+    conn: &Connection,
+    keyword: &str,
+    limit: i64,
+) -> Result<Vec<QuickSwitchItemDto>, AppError> {
+    let query = keyword.trim();
+    if query.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let name_pattern = format!("%{}%", escape_like(query));
+    let pinyin_pattern = format!("%{}%", escape_like(&query.to_lowercase()));
+    // 每类结果在 SQL 中截断前先把前缀命中排在前面，避免较早的前缀命中被较新的子串命中挤掉
+    let name_prefix = format!("{}%", escape_like(query));
+    let pinyin_prefix = format!("{}%", escape_like(&query.to_lowercase()));
+
+    let mut items: Vec<QuickSwitchItemDto> = Vec::new();
+
+    let mut stmt = conn.prepare(
+        "SELECT id, name FROM projects
+         WHERE deleted_at IS NULL
+           AND (name LIKE ?1 ESCAPE '\\' OR name_pinyin LIKE ?2 ESCAPE '\\' OR name_initials LIKE ?2 ESCAPE '\\')
+         ORDER BY (name LIKE ?4 ESCAPE '\\' OR name_pinyin LIKE ?5 ESCAPE '\\' OR name_initials LIKE ?5 ESCAPE '\\') DESC,
+                  created_at DESC
+         LIMIT ?3",
+    )?;
+    let projects = stmt.query_map(params![name_pattern, pinyin_pattern, limit, name_prefix, pinyin_prefix], |row| {
+        Ok(QuickSwitchItemDto {
+            kind: "project".to_string(),
+            id: row.get(0)?,
+            project_id: row.get(0)?,
+            task_id: None,
+            name: row.get(1)?,
+            context: None,
+            highlight: None,
+        })
+    })?;
+    for item in projects {
+        items.push(item?);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT t.id, t.project_id, t.name, p.name FROM tasks t
+         JOIN projects p ON t.project_id = p.id
+         WHERE t.deleted_at IS NULL AND p.deleted_at IS NULL
+           AND (t.name LIKE ?1 ESCAPE '\\' OR t.name_pinyin LIKE ?2 ESCAPE '\\' OR t.name_initials LIKE ?2 ESCAPE '\\')
+         ORDER BY (t.name LIKE ?4 ESCAPE '\\' OR t.name_pinyin LIKE ?5 ESCAPE '\\' OR t.name_initials LIKE ?5 ESCAPE '\\') DESC,
+                  t.created_at DESC
+         LIMIT ?3",
+    )?;
+    let tasks = stmt.query_map(params![name_pattern, pinyin_pattern, limit, name_prefix, pinyin_prefix], |row| {
+        Ok(QuickSwitchItemDto {
+            kind: "task".to_string(),
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            task_id: Some(row.get(0)?),
+            name: row.get(2)?,
+            context: Some(row.get(3)?),
+            highlight: None,
+        })
+    })?;
+    for item in tasks {
+        items.push(item?);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT pe.id, t.project_id, pe.task_id, pe.title, p.name, t.name FROM prompt_entries pe
+         JOIN tasks t ON pe.task_id = t.id
+         JOIN projects p ON t.project_id = p.id
+         WHERE pe.title IS NOT NULL
+           AND pe.deleted_at IS NULL AND t.deleted_at IS NULL AND p.deleted_at IS NULL
+           AND (pe.title LIKE ?1 ESCAPE '\\' OR pe.title_pinyin LIKE ?2 ESCAPE '\\' OR pe.title_initials LIKE ?2 ESCAPE '\\')
+         ORDER BY (pe.title LIKE ?4 ESCAPE '\\' OR pe.title_pinyin LIKE ?5 ESCAPE '\\' OR pe.title_initials LIKE ?5 ESCAPE '\\') DESC,
+                  pe.created_at DESC
+         LIMIT ?3",
+    )?;
+    let prompts = stmt.query_map(params![name_pattern, pinyin_pattern, limit, name_prefix, pinyin_prefix], |row| {
+        let project_name: String = row.get(4)?;
+        let task_name: String = row.get(5)?;
+        Ok(QuickSwitchItemDto {
+            kind: "prompt".to_string(),
+            id: row.get(0)?,
+            project_id: row.get(1)?,
+            task_id: Some(row.get(2)?),
+            name: row.get(3)?,
+            context: Some(format!("{} / {}", project_name, task_name)),
+            highlight: None,
+        })
+    })?;
+    for item in prompts {
+        items.push(item?);
+    }
+
+    // 计算高亮片段，并按是否为前缀命中排序（sort_by_key 是稳定排序，保留类型顺序）
+    let mut ranked: Vec<(bool, QuickSwitchItemDto)> = items
+        .into_iter()
+        .map(|mut item| {
+            let span = find_ignore_case(&item.name, query).or_else(|| find_pinyin_match(&item.name, query));
+            let is_prefix = matches!(span, Some((0, _)));
+            item.highlight = span.map(|(s, e)| item.name[s..e].to_string());
+            (is_prefix, item)
+        })
+        .collect();
+    ranked.sort_by_key(|(is_prefix, _)| !*is_prefix);
+
+    Ok(ranked
+        .into_iter()
+        .map(|(_, item)| item)
+        .take(limit as usize)
+        .collect())
+}
+
+/// trigram 分词器能够匹配的最短查询长度（字符数）
+const TRIGRAM_MIN_CHARS: usize = 3;
+/// bm25 中标题列的权重
+const TITLE_WEIGHT: f64 = 10.0;
+/// bm25 中内容列的权重
+const CONTENT_WEIGHT: f64 = 1.0;
+/// 摘要中匹配位置之前保留的字符数
+const SNIPPET_CHARS_BEFORE: usize = 30;
+/// 摘要中匹配位置之后保留的字符数
+const SNIPPET_CHARS_AFTER: usize = 70;
+
+/// 按空白拆分查询词
+fn split_terms(keyword: &str) -> Vec<String> {
+    keyword.split_whitespace().map(|s| s.to_string()).collect()
+}
+
+/// 将查询词转换为 FTS5 MATCH 表达式
+///
+/// 每个词作为一个短语（转义双引号，避免 FTS5 语法错误），词之间为 AND 关系。
+/// trigram 分词本身就是子串匹配，因此无需前缀通配符。
+fn build_match_query(terms: &[&String]) -> String {
+    terms
+        .iter()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 转义 LIKE 模式中的通配符
+fn escape_like(term: &str) -> String {
+    term.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+fn parse_search_row(
+    row: &rusqlite::Row,
+    keyword: &str,
+    terms: &[String],
+) -> rusqlite::Result<SearchResultDto> {
     let content: String = row.get(5)?;
     let title: Option<String> = row.get(6)?;
     let created_at: String = row.get(7)?;
 
     // 生成摘要：找到关键词附近的内容
-    let snippet = generate_snippet(&content, &title, keyword);
+    let (snippet, highlight) = generate_snippet(&content, &title, keyword, terms);
 
     Ok(SearchResultDto {
         project_id: row.get(0)?,
@@ -79,37 +346,81 @@ fn parse_search_row(row: &rusqlite::Row, keyword: &str) -> rusqlite::Result<Sear
         project_name: row.get(3)?,
         task_name: row.get(4)?,
         snippet,
+        highlight,
         created_at,
     })
 }
 
-/// 生成搜索结果摘要
-fn generate_snippet(content: &str, title: &Option<String>, keyword: &str) -> String {
-    // 先检查标题
-    if let Some(t) = title {
-        if t.to_lowercase().contains(&keyword.to_lowercase()) {
-            return format!("[标题] {}", t);
+/// 在 haystack 中忽略大小写查找 needle，返回原文中匹配片段的字节区间
+///
+/// 逐字符比较而不是先整体转小写再查找，保证返回的区间总是落在原文的字符边界上。
+fn find_ignore_case(haystack: &str, needle: &str) -> Option<(usize, usize)> {
+    if needle.is_empty() {
+        return None;
+    }
+
+    for (start, _) in haystack.char_indices() {
+        let mut hay_chars = haystack[start..].char_indices();
+        let mut matched = true;
+        let mut end = start;
+
+        for n in needle.chars() {
+            match hay_chars.next() {
+                Some((offset, h)) if h.to_lowercase().eq(n.to_lowercase()) => {
+                    end = start + offset + h.len_utf8();
+                }
+                _ => {
+                    matched = false;
+                    break;
+                }
+            }
+        }
+
+        if matched {
+            return Some((start, end));
         }
     }
 
-    // 在内容中查找关键词位置
-    let content_lower = content.to_lowercase();
-    let keyword_lower = keyword.to_lowercase();
+    None
+}
+
+/// 按优先级查找第一个命中的查询词：先整句，再按长度从长到短逐个尝试
+fn find_first_match(text: &str, keyword: &str, terms: &[String]) -> Option<(usize, usize)> {
+    let mut candidates: Vec<&str> = vec![keyword.trim()];
+    let mut sorted_terms: Vec<&str> = terms.iter().map(|s| s.as_str()).collect();
+    sorted_terms.sort_by_key(|t| std::cmp::Reverse(t.chars().count()));
+    candidates.extend(sorted_terms);
+
+    candidates
+        .into_iter()
+        .find_map(|candidate| find_ignore_case(text, candidate))
+}
 
-    if let Some(pos) = content_lower.find(&keyword_lower) {
-        // 提取关键词前后各 50 个字符
-        let start = pos.saturating_sub(50);
-        let end = (pos + keyword.len() + 50).min(content.len());
+/// 生成搜索结果摘要，同时返回摘要中实际命中的原文片段，供前端高亮
+fn generate_snippet(
+    content: &str,
+    title: &Option<String>,
+    keyword: &str,
+    terms: &[String],
+) -> (String, Option<String>) {
+    // 先检查标题
+    if let Some(t) = title {
+        if let Some((start, end)) = find_first_match(t, keyword, terms) {
+            return (format!("[标题] {}", t), Some(t[start..end].to_string()));
+        }
+    }
 
-        // 确保不会在字符边界切断
+    // 在内容中查找关键词位置，按字符（而非字节）截取前后文
+    if let Some((start, end)) = find_first_match(content, keyword, terms) {
         let snippet_start = content[..start]
             .char_indices()
-            .last()
+            .rev()
+            .nth(SNIPPET_CHARS_BEFORE.saturating_sub(1))
             .map(|(i, _)| i)
             .unwrap_or(0);
         let snippet_end = content[end..]
             .char_indices()
-            .next()
+            .nth(SNIPPET_CHARS_AFTER)
             .map(|(i, _)| end + i)
             .unwrap_or(content.len());
 
@@ -122,14 +433,14 @@ fn generate_snippet(content: &str, title: &Option<String>, keyword: &str) -> Str
             snippet = format!("{}...", snippet);
         }
 
-        snippet
+        (snippet, Some(content[start..end].to_string()))
     } else {
         // 如果找不到，返回前 100 个字符
         let end = content.char_indices().nth(100).map(|(i, _)| i).unwrap_or(content.len());
         if end < content.len() {
-            format!("{}...", &content[..end])
+            (format!("{}...", &content[..end]), None)
         } else {
-            content.to_string()
+            (content.to_string(), None)
         }
     }
 }
@@ -0,0 +1,357 @@
+use crate::error::AppError;
+use crate::models::ExportScope;
+use crate::services::backup_service::{self, ExportData, ExportPrompt};
+use crate::utils::hash::sha256_hex;
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+/// 提示词没有标题时，取内容开头作为标题的字符数
+const TITLE_FALLBACK_CHARS: usize = 40;
+/// 标签页文件名使用的标签名哈希长度（十六进制字符数）
+const TAG_HASH_CHARS: usize = 12;
+/// 由导出生成的子目录，每次导出前清空，避免残留已删除任务或标签的旧页面
+const GENERATED_DIRS: &[&str] = &["tasks", "tags"];
+
+const STYLE_CSS: &str = r#"body { margin: 0; font: 15px/1.6 -apple-system, "Segoe UI", "PingFang SC", "Microsoft YaHei", sans-serif; color: #222; background: #fafafa; }
+header { padding: 12px 24px; background: #fff; border-bottom: 1px solid #e5e5e5; }
+header a { margin-right: 16px; color: #333; text-decoration: none; font-weight: 600; }
+main { max-width: 960px; margin: 0 auto; padding: 24px; }
+a { color: #2563eb; }
+h1 { margin-top: 0; }
+ul.tasks, ul.results, ul.entries { padding-left: 20px; }
+.count, .meta { color: #888; font-size: 13px; }
+.prompt { margin: 20px 0; padding: 16px; background: #fff; border: 1px solid #e5e5e5; border-radius: 6px; }
+.prompt h3 { margin: 0 0 4px; }
+.prompt h3 a { color: inherit; text-decoration: none; }
+.prompt pre { margin: 12px 0 0; white-space: pre-wrap; word-break: break-word; font: 14px/1.6 ui-monospace, Menlo, Consolas, monospace; }
+.tag { display: inline-block; margin: 4px 6px 0 0; padding: 0 8px; background: #eef2ff; border-radius: 10px; font-size: 13px; text-decoration: none; }
+#search { width: 100%; box-sizing: border-box; padding: 8px 12px; font-size: 15px; border: 1px solid #ccc; border-radius: 6px; }
+"#;
+
+const SEARCH_JS: &str = r#"(function () {
+    var input = document.getElementById("search");
+    var results = document.getElementById("results");
+    var entries = (window.PROMPTLOG_SEARCH_INDEX || []).map(function (e) {
+        return { entry: e, text: [e.title, e.project, e.task, e.tags.join(" "), e.content].join("\n").toLowerCase() };
+    });
+
+    input.addEventListener("input", function () {
+        var terms = input.value.toLowerCase().split(/\s+/).filter(Boolean);
+        results.innerHTML = "";
+        if (!terms.length) return;
+
+        var matches = entries.filter(function (item) {
+            return terms.every(function (t) { return item.text.indexOf(t) !== -1; });
+        });
+        matches.slice(0, 100).forEach(function (item) {
+            var li = document.createElement("li");
+            var link = document.createElement("a");
+            link.href = item.entry.url;
+            link.textContent = item.entry.title;
+            var meta = document.createElement("span");
+            meta.className = "meta";
+            meta.textContent = " " + item.entry.project + " / " + item.entry.task;
+            li.appendChild(link);
+            li.appendChild(meta);
+            results.appendChild(li);
+        });
+        if (!matches.length) {
+            var empty = document.createElement("li");
+            empty.textContent = "没有匹配的提示词";
+            results.appendChild(empty);
+        }
+    });
+})();
+"#;
+
+/// 搜索索引中的一条提示词
+#[derive(Serialize)]
+struct SearchEntry<'a> {
+    title: String,
+    project: &'a str,
+    task: &'a str,
+    tags: &'a [String],
+    content: &'a str,
+    url: String,
+}
+
+/// 标签页中的一条提示词
+struct TagEntry<'a> {
+    title: String,
+    project: &'a str,
+    task: &'a str,
+    url: String,
+}
+
+/// 导出为静态网站，返回写入的文件数
+///
+/// 包含项目索引页、每个任务一页（每条提示词有锚点）、标签索引页和每个标签一页，
+/// 以及供首页搜索框使用的索引脚本。不引用任何外部资源，整个目录可直接打包托管，也可本地打开。
+/// 指定 `scope` 时只导出范围内的数据。目标目录中的 `tasks/` 和 `tags/` 会先被清空。
+pub fn export_site(conn: &Connection, dir: &Path, scope: Option<&ExportScope>) -> Result<usize, AppError> {
+    let data = match scope {
+        Some(scope) => backup_service::collect_scoped_export_data(conn, scope)?,
+        None => backup_service::collect_export_data(conn)?,
+    };
+
+    let files = render_site(&data)?;
+    for name in GENERATED_DIRS {
+        match fs::remove_dir_all(dir.join(name)) {
+            Err(e) if e.kind() != ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
+    }
+    for (path, content) in &files {
+        let path = dir.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
+    }
+
+    Ok(files.len())
+}
+
+/// 生成网站的全部文件：相对路径和内容
+fn render_site(data: &ExportData) -> Result<Vec<(String, String)>, AppError> {
+    // 任务页文件名优先使用 uuid，重新导出后链接保持不变
+    let mut used = HashSet::new();
+    let task_files: Vec<Vec<String>> = data
+        .projects
+        .iter()
+        .map(|project| {
+            project
+                .tasks
+                .iter()
+                .map(|task| {
+                    let id = task
+                        .uuid
+                        .clone()
+                        .filter(|u| is_safe_id(u) && !used.contains(u))
+                        .unwrap_or_else(|| format!("task-{}", used.len() + 1));
+                    used.insert(id.clone());
+                    format!("tasks/{}.html", id)
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut tags: BTreeMap<&str, Vec<TagEntry>> = BTreeMap::new();
+    let mut search = Vec::new();
+    for (project, files) in data.projects.iter().zip(&task_files) {
+        for (task, file) in project.tasks.iter().zip(files) {
+            for (index, prompt) in task.prompts.iter().enumerate() {
+                let url = format!("{}#{}", file, anchor(prompt, index));
+
+                for tag in prompt.tags.as_deref().unwrap_or_default() {
+                    tags.entry(tag).or_default().push(TagEntry {
+                        title: display_title(prompt),
+                        project: &project.name,
+                        task: &task.name,
+                        url: url.clone(),
+                    });
+                }
+                search.push(SearchEntry {
+                    title: display_title(prompt),
+                    project: &project.name,
+                    task: &task.name,
+                    tags: prompt.tags.as_deref().unwrap_or_default(),
+                    content: &prompt.content,
+                    url,
+                });
+            }
+        }
+    }
+    // 标签页文件名取自标签名的哈希，增删其他标签时链接保持不变
+    let tag_files: BTreeMap<&str, String> = tags
+        .keys()
+        .map(|tag| (*tag, format!("tags/{}.html", &sha256_hex(tag.as_bytes())[..TAG_HASH_CHARS])))
+        .collect();
+
+    let mut files = vec![
+        ("assets/style.css".to_string(), STYLE_CSS.to_string()),
+        ("assets/search.js".to_string(), SEARCH_JS.to_string()),
+        (
+            "search-index.js".to_string(),
+            format!("window.PROMPTLOG_SEARCH_INDEX = {};\n", serde_json::to_string(&search)?),
+        ),
+        ("index.html".to_string(), render_index(data, &task_files)),
+        ("tags.html".to_string(), render_tag_index(&tags, &tag_files)),
+    ];
+
+    for (project, files_of_project) in data.projects.iter().zip(&task_files) {
+        for (task, file) in project.tasks.iter().zip(files_of_project) {
+            let mut body = format!(
+                "<p class=\"meta\"><a href=\"../index.html\">{}</a></p>\n<h1>{}</h1>\n",
+                escape(&project.name),
+                escape(&task.name)
+            );
+            if let Some(description) = task.description.as_deref().filter(|d| !d.trim().is_empty()) {
+                body.push_str(&format!("<p>{}</p>\n", escape(description)));
+            }
+            for (index, prompt) in task.prompts.iter().enumerate() {
+                body.push_str(&render_prompt(prompt, index, &tag_files));
+            }
+
+            files.push((file.clone(), page(&task.name, "../", &body, false)));
+        }
+    }
+
+    for (tag, entries) in &tags {
+        let mut body = format!("<h1>{}</h1>\n<ul class=\"entries\">\n", escape(tag));
+        for entry in entries {
+            body.push_str(&format!(
+                "<li><a href=\"../{}\">{}</a> <span class=\"meta\">{} / {}</span></li>\n",
+                escape(&entry.url),
+                escape(&entry.title),
+                escape(entry.project),
+                escape(entry.task)
+            ));
+        }
+        body.push_str("</ul>\n");
+
+        files.push((tag_files[tag].clone(), page(tag, "../", &body, false)));
+    }
+
+    Ok(files)
+}
+
+fn render_index(data: &ExportData, task_files: &[Vec<String>]) -> String {
+    let mut body = String::from(
+        "<h1>提示词库</h1>\n<input id=\"search\" type=\"search\" placeholder=\"搜索标题、内容、标签…\" autofocus>\n\
+         <ul id=\"results\" class=\"results\"></ul>\n",
+    );
+
+    for (project, files) in data.projects.iter().zip(task_files) {
+        body.push_str(&format!("<h2>{}</h2>\n<ul class=\"tasks\">\n", escape(&project.name)));
+        for (task, file) in project.tasks.iter().zip(files) {
+            body.push_str(&format!(
+                "<li><a href=\"{}\">{}</a> <span class=\"count\">{} 条</span></li>\n",
+                escape(file),
+                escape(&task.name),
+                task.prompts.len()
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+
+    page("提示词库", "", &body, true)
+}
+
+fn render_tag_index(tags: &BTreeMap<&str, Vec<TagEntry>>, tag_files: &BTreeMap<&str, String>) -> String {
+    let mut body = String::from("<h1>标签</h1>\n<p>\n");
+    for (tag, entries) in tags {
+        body.push_str(&format!(
+            "<a class=\"tag\" href=\"{}\">{} <span class=\"count\">{}</span></a>\n",
+            escape(&tag_files[tag]),
+            escape(tag),
+            entries.len()
+        ));
+    }
+    body.push_str("</p>\n");
+
+    page("标签", "", &body, false)
+}
+
+fn render_prompt(prompt: &ExportPrompt, index: usize, tag_files: &BTreeMap<&str, String>) -> String {
+    let id = anchor(prompt, index);
+    let mut meta = Vec::new();
+    if let Some(model) = prompt.model.as_deref().filter(|m| !m.is_empty()) {
+        meta.push(escape(model));
+    }
+    meta.push(escape(prompt.created_at.get(..10).unwrap_or(&prompt.created_at)));
+
+    let tags: String = prompt
+        .tags
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(|tag| {
+            format!(
+                "<a class=\"tag\" href=\"../{}\">{}</a>",
+                escape(&tag_files[tag.as_str()]),
+                escape(tag)
+            )
+        })
+        .collect();
+
+    format!(
+        "<article class=\"prompt\" id=\"{id}\">\n<h3><a href=\"#{id}\">{}</a></h3>\n\
+         <div class=\"meta\">{}</div>\n<div>{}</div>\n<pre>{}</pre>\n</article>\n",
+        escape(&display_title(prompt)),
+        meta.join(" · "),
+        tags,
+        escape(&prompt.content),
+        id = escape(&id),
+    )
+}
+
+/// 页面框架；`root` 为到网站根目录的相对路径，首页额外引入搜索脚本
+fn page(title: &str, root: &str, body: &str, with_search: bool) -> String {
+    let scripts = if with_search {
+        format!(
+            "<script src=\"{root}search-index.js\"></script>\n<script src=\"{root}assets/search.js\"></script>\n",
+            root = root
+        )
+    } else {
+        String::new()
+    };
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"zh-CN\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title}</title>\n<link rel=\"stylesheet\" href=\"{root}assets/style.css\">\n</head>\n<body>\n\
+         <header><a href=\"{root}index.html\">首页</a><a href=\"{root}tags.html\">标签</a></header>\n\
+         <main>\n{body}</main>\n{scripts}</body>\n</html>\n",
+        title = escape(title),
+        root = root,
+        body = body,
+        scripts = scripts,
+    )
+}
+
+/// 提示词锚点：有 uuid 时使用 uuid，否则按任务内的序号
+fn anchor(prompt: &ExportPrompt, index: usize) -> String {
+    match prompt.uuid.as_deref().filter(|u| is_safe_id(u)) {
+        Some(uuid) => format!("p-{}", uuid),
+        None => format!("p-{}", index + 1),
+    }
+}
+
+/// 提示词标题，没有标题时取内容开头
+fn display_title(prompt: &ExportPrompt) -> String {
+    if let Some(title) = prompt.title.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        return title.to_string();
+    }
+
+    let first_line = prompt.content.trim().lines().next().unwrap_or("");
+    let mut title: String = first_line.chars().take(TITLE_FALLBACK_CHARS).collect();
+    if first_line.chars().count() > TITLE_FALLBACK_CHARS {
+        title.push('…');
+    }
+    title
+}
+
+/// 可直接用作文件名和锚点的 ID
+fn is_safe_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,180 @@
+use crate::db::{establish_connection, init_db, migrations};
+use crate::error::AppError;
+use crate::models::SnapshotDto;
+use crate::utils::file_name::sanitize_file_name;
+use chrono::{Local, NaiveDateTime, TimeZone};
+use log::info;
+use rusqlite::backup::Progress;
+use rusqlite::{Connection, DatabaseName, OpenFlags};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const MANUAL_PREFIX: &str = "snapshot-";
+const AUTO_PREFIX: &str = "auto-";
+const FILE_SUFFIX: &str = ".db";
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+/// 时间戳部分的长度，如 20240101-093000
+const TIMESTAMP_LEN: usize = 15;
+
+/// 自动快照最多保留的份数，手动快照不会自动删除
+const MAX_AUTO_SNAPSHOTS: usize = 20;
+
+/// 快照目录：数据库所在目录下的 snapshots/
+pub fn snapshot_dir(db_path: &Path) -> Result<PathBuf, AppError> {
+    db_path
+        .parent()
+        .map(|p| p.join("snapshots"))
+        .ok_or_else(|| AppError::Unknown("无法确定数据库所在目录".to_string()))
+}
+
+/// 创建快照
+///
+/// 使用 `VACUUM INTO` 写出一致的数据库副本，WAL 中尚未写回的内容也包含在内；在读连接上执行时不阻塞写操作。
+/// 自动快照超过保留份数时删除最旧的。
+pub fn create_snapshot(conn: &Connection, dir: &Path, name: &str, automatic: bool) -> Result<SnapshotDto, AppError> {
+    fs::create_dir_all(dir)?;
+
+    let name = sanitize_file_name(name, "快照");
+    let prefix = if automatic { AUTO_PREFIX } else { MANUAL_PREFIX };
+    let stem = format!("{}{}-{}", prefix, Local::now().naive_local().format(TIMESTAMP_FORMAT), name);
+
+    // 同一秒内重名时追加序号
+    let mut path = dir.join(format!("{}{}", stem, FILE_SUFFIX));
+    let mut n = 2;
+    while path.exists() {
+        path = dir.join(format!("{} ({}){}", stem, n, FILE_SUFFIX));
+        n += 1;
+    }
+
+    conn.execute("VACUUM INTO ?1", [path.to_string_lossy()])?;
+    let snapshot = snapshot_info(&path).ok_or_else(|| AppError::Unknown("无法读取新快照".to_string()))?;
+
+    if automatic {
+        let removed = prune(dir, &snapshot.file_name)?;
+        if removed > 0 {
+            info!("已删除 {} 个旧的自动快照", removed);
+        }
+    }
+
+    Ok(snapshot)
+}
+
+/// 列出目录中的快照，最新的在前；不符合命名规则的文件会被忽略
+pub fn list_snapshots(dir: &Path) -> Result<Vec<SnapshotDto>, AppError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut snapshots: Vec<(NaiveDateTime, SnapshotDto)> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let (time, _, _) = parse_file_name(&path.file_name()?.to_string_lossy())?;
+            Some((time, snapshot_info(&path)?))
+        })
+        .collect();
+    snapshots.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.1.file_name.cmp(&a.1.file_name)));
+
+    Ok(snapshots.into_iter().map(|(_, snapshot)| snapshot).collect())
+}
+
+/// 从快照恢复，替换传入的写连接，返回恢复前为当前数据自动创建的快照
+///
+/// 先校验快照文件并为当前数据创建快照；再用 SQLite 在线备份接口将快照写回数据库文件，
+/// 升级到当前表结构后替换写连接。读连接在 WAL 模式下随后即可读到恢复后的数据。
+pub fn restore_snapshot(
+    writer: &mut Connection,
+    db_path: &Path,
+    dir: &Path,
+    file_name: &str,
+) -> Result<SnapshotDto, AppError> {
+    if parse_file_name(file_name).is_none() || file_name.contains(['/', '\\']) {
+        return Err(AppError::ValidationError(format!("无效的快照文件名: {}", file_name)));
+    }
+    let path = dir.join(file_name);
+    if !path.is_file() {
+        return Err(AppError::NotFound(format!("快照 {} 不存在", file_name)));
+    }
+
+    verify_snapshot(&path)?;
+
+    let safety = create_snapshot(writer, dir, "恢复前", true)?;
+    info!("恢复快照前已创建快照: {}", safety.file_name);
+
+    let mut restored = establish_connection(&db_path.to_path_buf())?;
+    restored.restore(DatabaseName::Main, &path, None::<fn(Progress)>)?;
+    // 较早版本的快照恢复后需要升级表结构
+    init_db(&mut restored)?;
+
+    *writer = restored;
+
+    Ok(safety)
+}
+
+/// 恢复前检查快照文件完整，且不是由更新版本的应用创建
+fn verify_snapshot(path: &Path) -> Result<(), AppError> {
+    let corrupted = |e: rusqlite::Error| AppError::BackupCorrupted(format!("快照无法读取: {}", e));
+
+    // FTS5 表的完整性检查需要可写连接，检查本身不会修改文件内容
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE).map_err(corrupted)?;
+    let result: String = conn
+        .query_row("PRAGMA quick_check", [], |row| row.get(0))
+        .map_err(corrupted)?;
+    if result != "ok" {
+        return Err(AppError::BackupCorrupted(format!("快照校验失败: {}", result)));
+    }
+
+    migrations::ensure_supported(&conn)?;
+
+    Ok(())
+}
+
+/// 按时间保留最新的若干份自动快照（包括刚创建的 `latest`），返回删除的数量
+fn prune(dir: &Path, latest: &str) -> Result<usize, AppError> {
+    let mut removed = 0;
+    for snapshot in list_snapshots(dir)?
+        .into_iter()
+        .filter(|s| s.automatic && s.file_name != latest)
+        .skip(MAX_AUTO_SNAPSHOTS - 1)
+    {
+        fs::remove_file(dir.join(&snapshot.file_name))?;
+        removed += 1;
+    }
+
+    Ok(removed)
+}
+
+/// 解析快照文件名，返回时间、名称和是否为自动快照
+fn parse_file_name(file_name: &str) -> Option<(NaiveDateTime, String, bool)> {
+    let (rest, automatic) = match file_name.strip_prefix(AUTO_PREFIX) {
+        Some(rest) => (rest, true),
+        None => (file_name.strip_prefix(MANUAL_PREFIX)?, false),
+    };
+    let rest = rest.strip_suffix(FILE_SUFFIX)?;
+
+    let timestamp = rest.get(..TIMESTAMP_LEN)?;
+    let name = rest.get(TIMESTAMP_LEN..)?.strip_prefix('-')?;
+    let time = NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT).ok()?;
+
+    Some((time, name.to_string(), automatic))
+}
+
+fn snapshot_info(path: &Path) -> Option<SnapshotDto> {
+    let file_name = path.file_name()?.to_string_lossy().to_string();
+    let (time, name, automatic) = parse_file_name(&file_name)?;
+    let metadata = fs::metadata(path).ok().filter(|m| m.is_file())?;
+    let created_at = Local
+        .from_local_datetime(&time)
+        .earliest()
+        .map(|t| t.to_rfc3339())
+        .unwrap_or_else(|| time.to_string());
+
+    Some(SnapshotDto {
+        file_name,
+        name,
+        created_at,
+        size: metadata.len(),
+        automatic,
+    })
+}
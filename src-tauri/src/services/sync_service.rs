@@ -0,0 +1,912 @@
+use crate::db::DbState;
+use crate::error::AppError;
+use crate::models::{PromptEntryDto, SyncConflictDto, SyncResolution, TrashKind};
+use crate::repositories::sync_repository::{self, SyncRecord};
+use crate::repositories::{
+    project_repository, prompt_repository, settings_repository, task_repository, trash_repository,
+};
+use crate::services::markdown_service::{self, PromptFrontMatter, UNSORTED_NAME};
+use crate::utils::file_name::sanitize_file_name;
+use crate::utils::hash::sha256_hex;
+use chrono::Utc;
+use log::{error, info};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashSet;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use walkdir::WalkDir;
+
+/// 同步文件夹的设置键
+const SYNC_FOLDER_KEY: &str = "sync_folder";
+/// 检测到同步冲突时发送的事件名，载荷为 `SyncConflictDto`
+pub const SYNC_CONFLICT_EVENT: &str = "sync-conflict";
+/// 文件修改已写回数据库时发送的事件名，载荷为提示词 ID
+pub const SYNC_APPLIED_EVENT: &str = "sync-applied";
+
+/// 一次同步操作的结果
+#[derive(Debug, Clone)]
+pub enum SyncOutcome {
+    Unchanged,
+    /// 数据库内容已写出到文件
+    Written,
+    /// 文件已被删除
+    Removed,
+    /// 文件修改已写回数据库（提示词 ID）
+    Applied(i64),
+    Conflict(SyncConflictDto),
+}
+
+/// 需要同步的范围
+#[derive(Debug, Clone, Copy)]
+pub enum SyncScope {
+    /// 单个提示词
+    Prompt(i64),
+    /// 全部数据，用于项目/任务改名、回收站和导入等批量变更
+    All,
+}
+
+/// 正在运行的文件夹同步，由 Tauri 管理
+#[derive(Default)]
+pub struct SyncHandle(Mutex<Option<ActiveSync>>);
+
+struct ActiveSync {
+    root: PathBuf,
+    _watcher: RecommendedWatcher,
+}
+
+/// 获取同步文件夹，未设置时返回 None
+pub fn get_sync_folder(conn: &Connection) -> Result<Option<String>, AppError> {
+    Ok(settings_repository::get_setting(conn, SYNC_FOLDER_KEY)?.filter(|v| !v.is_empty()))
+}
+
+/// 设置同步文件夹（None 表示关闭同步）；更换文件夹时清空原有同步状态
+pub fn set_sync_folder(conn: &Connection, folder: Option<&str>) -> Result<(), AppError> {
+    let folder = folder.map(str::trim).filter(|f| !f.is_empty());
+    if let Some(folder) = folder {
+        if !Path::new(folder).is_absolute() {
+            return Err(AppError::ValidationError("同步文件夹必须是绝对路径".to_string()));
+        }
+    }
+
+    if get_sync_folder(conn)?.as_deref() != folder {
+        sync_repository::clear_records(conn)?;
+    }
+    settings_repository::set_setting(conn, SYNC_FOLDER_KEY, folder.unwrap_or(""))
+}
+
+/// 将单个提示词的数据库状态写出到同步文件夹
+///
+/// 提示词已删除时移除对应文件；文件在外部也被修改过时标记冲突，不覆盖文件。
+pub fn export_prompt(conn: &Connection, root: &Path, prompt_id: i64) -> Result<SyncOutcome, AppError> {
+    let record = sync_repository::get_record(conn, prompt_id)?;
+    if record.as_ref().is_some_and(|r| r.conflict.is_some()) {
+        return Ok(SyncOutcome::Unchanged);
+    }
+
+    let Some(prompt) = load_visible_prompt(conn, prompt_id)? else {
+        let Some(record) = record else {
+            return Ok(SyncOutcome::Unchanged);
+        };
+        let path = root.join(&record.path);
+        return match file_hash(&path)? {
+            Some(hash) if hash != record.content_hash => conflict(
+                conn,
+                &record.path,
+                prompt_id,
+                None,
+                &hash,
+                "提示词已删除，但文件在外部被修改",
+            ),
+            Some(_) => {
+                fs::remove_file(&path)?;
+                sync_repository::delete_record(conn, prompt_id)?;
+                Ok(SyncOutcome::Removed)
+            }
+            None => {
+                sync_repository::delete_record(conn, prompt_id)?;
+                Ok(SyncOutcome::Unchanged)
+            }
+        };
+    };
+
+    let markdown = markdown_service::render_markdown(&PromptFrontMatter::from(&prompt), &prompt.content)?;
+    let new_hash = sha256_hex(markdown.as_bytes());
+    let target = target_path(conn, &prompt)?;
+    let version = db_version(&prompt);
+
+    // 按渲染结果比较而不是按修改时间：标签改名、合并等操作不会更新提示词的修改时间
+    if let Some(record) = &record {
+        if record.content_hash == new_hash && record.path == target {
+            return Ok(SyncOutcome::Unchanged);
+        }
+
+        let old_path = root.join(&record.path);
+        match file_hash(&old_path)? {
+            Some(hash) if hash != record.content_hash => {
+                return conflict(
+                    conn,
+                    &record.path,
+                    prompt_id,
+                    prompt.title.clone(),
+                    &hash,
+                    "提示词和文件都有修改",
+                );
+            }
+            None if record.content_hash != new_hash => {
+                return conflict(
+                    conn,
+                    &record.path,
+                    prompt_id,
+                    prompt.title.clone(),
+                    "",
+                    "文件已在外部删除，但提示词有新的修改",
+                );
+            }
+            Some(_) if record.path != target => fs::remove_file(&old_path)?,
+            _ => {}
+        }
+    }
+
+    // 目标位置已有其他内容的文件时不覆盖
+    let target_file = root.join(&target);
+    let tracked_here = record.as_ref().is_some_and(|r| r.path == target);
+    if !tracked_here {
+        if let Some(hash) = file_hash(&target_file)? {
+            if hash != new_hash {
+                return conflict(
+                    conn,
+                    &target,
+                    prompt_id,
+                    prompt.title.clone(),
+                    &hash,
+                    "同步文件夹中已存在同名文件",
+                );
+            }
+        }
+    }
+
+    write_file(&target_file, &markdown)?;
+    sync_repository::save_record(conn, prompt_id, &target, &new_hash, &version)?;
+
+    Ok(SyncOutcome::Written)
+}
+
+/// 将同步文件夹中新建或修改的文件写回数据库
+///
+/// 已跟踪的文件更新对应提示词（旧版本进入修订历史）；未跟踪的文件按所在目录新建提示词。
+/// 提示词在上次同步后也被修改过时标记冲突。
+pub fn apply_file(conn: &Connection, root: &Path, relative: &str) -> Result<SyncOutcome, AppError> {
+    let path = root.join(relative);
+    let bytes = match fs::read(&path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == ErrorKind::NotFound => return apply_removal(conn, root, relative),
+        Err(e) => return Err(e.into()),
+    };
+    let hash = sha256_hex(&bytes);
+
+    let mut record = sync_repository::get_record_by_path(conn, relative)?;
+    if record.as_ref().is_some_and(|r| r.conflict.is_some() || r.content_hash == hash) {
+        return Ok(SyncOutcome::Unchanged);
+    }
+
+    let parsed = String::from_utf8(bytes)
+        .map_err(|_| AppError::ValidationError("文件不是 UTF-8 编码".to_string()))
+        .and_then(|text| markdown_service::parse_markdown(&text));
+    let (front, content) = match parsed {
+        Ok(parsed) if !parsed.1.trim().is_empty() => parsed,
+        Ok(_) => return file_error(conn, record, relative, &hash, "文件内容为空".to_string()),
+        Err(e) => return file_error(conn, record, relative, &hash, e.to_string()),
+    };
+
+    // 未跟踪但带有 uuid 的文件：原文件已不存在时视为在外部移动或改名
+    if record.is_none() {
+        if let Some(moved) = find_moved_record(conn, root, front.uuid.as_deref())? {
+            sync_repository::save_record(conn, moved.prompt_id, relative, &moved.content_hash, &moved.db_version)?;
+            if moved.content_hash == hash {
+                return Ok(SyncOutcome::Unchanged);
+            }
+            record = Some(SyncRecord {
+                path: relative.to_string(),
+                ..moved
+            });
+        }
+    }
+
+    let Some(record) = record else {
+        // 提示词和同步记录一起写入，否则下次同步会把仍未跟踪的文件再导入一次
+        let tx = conn.is_autocommit().then(|| conn.unchecked_transaction()).transpose()?;
+        let task_id = task_for_path(conn, relative)?;
+        let prompt = prompt_repository::create_prompt(
+            conn,
+            task_id,
+            front.title.as_deref(),
+            &content,
+            Some(&front.tags),
+            front.model.as_deref(),
+        )?;
+        sync_repository::save_record(conn, prompt.id, relative, &hash, &db_version(&prompt))?;
+        if let Some(tx) = tx {
+            tx.commit()?;
+        }
+        return Ok(SyncOutcome::Applied(prompt.id));
+    };
+
+    let Some(current) = load_visible_prompt(conn, record.prompt_id)? else {
+        return conflict(conn, relative, record.prompt_id, front.title, &hash, "文件在外部被修改，但提示词已删除");
+    };
+    if db_version(&current) != record.db_version {
+        return conflict(conn, relative, record.prompt_id, current.title, &hash, "提示词和文件都有修改");
+    }
+
+    prompt_repository::write_prompt(
+        conn,
+        &current,
+        front.title.as_deref(),
+        &content,
+        Some(&front.tags),
+        front.model.as_deref(),
+    )?;
+    let updated = prompt_repository::get_prompt(conn, record.prompt_id)?;
+    sync_repository::save_record(conn, record.prompt_id, relative, &hash, &db_version(&updated))?;
+
+    Ok(SyncOutcome::Applied(record.prompt_id))
+}
+
+/// 同步文件夹中的文件被删除：将对应提示词移入回收站
+///
+/// 同步记录暂时保留，改名产生的“删除 + 新建”事件可据此把提示词找回。
+pub fn apply_removal(conn: &Connection, root: &Path, relative: &str) -> Result<SyncOutcome, AppError> {
+    let Some(record) = sync_repository::get_record_by_path(conn, relative)? else {
+        return Ok(SyncOutcome::Unchanged);
+    };
+    if record.conflict.is_some() || root.join(relative).exists() {
+        return Ok(SyncOutcome::Unchanged);
+    }
+
+    let Some(current) = load_visible_prompt(conn, record.prompt_id)? else {
+        sync_repository::delete_record(conn, record.prompt_id)?;
+        return Ok(SyncOutcome::Unchanged);
+    };
+    if db_version(&current) != record.db_version {
+        return conflict(
+            conn,
+            relative,
+            record.prompt_id,
+            current.title,
+            "",
+            "文件已在外部删除，但提示词有新的修改",
+        );
+    }
+
+    prompt_repository::delete_prompt(conn, record.prompt_id)?;
+
+    Ok(SyncOutcome::Applied(record.prompt_id))
+}
+
+/// 完整比对数据库与同步文件夹
+///
+/// 先把文件侧的修改写回数据库，再写出数据库侧的修改，最后导入文件夹中未跟踪的新文件。
+pub fn full_sync(conn: &Connection, root: &Path) -> Result<Vec<SyncOutcome>, AppError> {
+    fs::create_dir_all(root)?;
+    let mut outcomes = Vec::new();
+
+    let records = sync_repository::list_records(conn)?;
+    let mut prompt_ids: Vec<i64> = conn
+        .prepare(
+            "SELECT pe.id FROM prompt_entries pe
+             JOIN tasks t ON pe.task_id = t.id AND t.deleted_at IS NULL
+             JOIN projects p ON t.project_id = p.id AND p.deleted_at IS NULL
+             WHERE pe.deleted_at IS NULL ORDER BY pe.id",
+        )?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // 单个文件出错时记录日志后继续，出错文件对应的提示词本轮不再写出，避免覆盖文件侧的修改
+    let mut seen = HashSet::new();
+    for record in &records {
+        prompt_ids.push(record.prompt_id);
+        if record.conflict.is_some() {
+            continue;
+        }
+
+        let result = file_hash(&root.join(&record.path)).and_then(|hash| {
+            if hash.as_deref() == Some(&record.content_hash) {
+                return Ok(None);
+            }
+            apply_file(conn, root, &record.path).map(Some)
+        });
+        match result {
+            Ok(outcome) => outcomes.extend(outcome),
+            Err(e) => {
+                error!("同步文件失败 {}: {:?}", record.path, e);
+                seen.insert(record.prompt_id);
+            }
+        }
+    }
+
+    for prompt_id in prompt_ids {
+        if seen.insert(prompt_id) {
+            match export_prompt(conn, root, prompt_id) {
+                Ok(outcome) => outcomes.push(outcome),
+                Err(e) => error!("写出提示词 {} 失败: {:?}", prompt_id, e),
+            }
+        }
+    }
+
+    let tracked: HashSet<String> = sync_repository::list_records(conn)?
+        .into_iter()
+        .map(|r| r.path)
+        .collect();
+    let walker = WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !markdown_service::is_hidden(e))
+        .filter_map(Result::ok);
+    for entry in walker {
+        if !entry.file_type().is_file() || !markdown_service::is_importable(entry.path()) {
+            continue;
+        }
+        let relative = relative_path(root, entry.path());
+        if !tracked.contains(&relative) {
+            match apply_file(conn, root, &relative) {
+                Ok(outcome) => outcomes.push(outcome),
+                Err(e) => error!("导入同步文件失败 {}: {:?}", relative, e),
+            }
+        }
+    }
+
+    Ok(outcomes)
+}
+
+/// 按用户选择解决冲突
+pub fn resolve_conflict(
+    conn: &Connection,
+    root: &Path,
+    prompt_id: i64,
+    keep: SyncResolution,
+) -> Result<(), AppError> {
+    let record = sync_repository::get_record(conn, prompt_id)?
+        .filter(|r| r.conflict.is_some())
+        .ok_or_else(|| AppError::NotFound(format!("提示词 {} 没有同步冲突", prompt_id)))?;
+    let path = root.join(&record.path);
+
+    match keep {
+        SyncResolution::Database => {
+            if load_visible_prompt(conn, prompt_id)?.is_none() {
+                if path.exists() {
+                    fs::remove_file(&path)?;
+                }
+                sync_repository::delete_record(conn, prompt_id)?;
+                return Ok(());
+            }
+
+            // 以当前文件为基准重新写出，文件中的内容不再视为外部修改
+            match file_hash(&path)? {
+                Some(hash) => sync_repository::save_record(conn, prompt_id, &record.path, &hash, "")?,
+                None => sync_repository::delete_record(conn, prompt_id)?,
+            }
+            if let SyncOutcome::Conflict(c) = export_prompt(conn, root, prompt_id)? {
+                return Err(AppError::SyncError(c.reason));
+            }
+        }
+        SyncResolution::File => {
+            if !path.exists() {
+                if load_visible_prompt(conn, prompt_id)?.is_some() {
+                    prompt_repository::delete_prompt(conn, prompt_id)?;
+                }
+                sync_repository::delete_record(conn, prompt_id)?;
+                return Ok(());
+            }
+
+            // 以当前数据库状态为基准，文件内容视为新的外部修改
+            if prompt_repository::get_prompt(conn, prompt_id).is_err() {
+                trash_repository::restore_item(conn, TrashKind::Prompt, prompt_id)?;
+            }
+            let current = load_visible_prompt(conn, prompt_id)?
+                .ok_or_else(|| AppError::ValidationError("所属任务或项目在回收站中，请先恢复上级".to_string()))?;
+            sync_repository::save_record(conn, prompt_id, &record.path, "", &db_version(&current))?;
+
+            if let SyncOutcome::Conflict(c) = apply_file(conn, root, &record.path)? {
+                return Err(AppError::SyncError(c.reason));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 读取设置并启动同步：完整比对一次后开始监听文件夹，返回检测到的冲突
+pub fn start(app: &AppHandle) -> Result<Vec<SyncConflictDto>, AppError> {
+    stop(app);
+
+    let db = app.state::<DbState>();
+    let folder = {
+        let conn = db.write().map_err(|e| AppError::SyncError(e.message))?;
+        get_sync_folder(&conn)?
+    };
+    let Some(folder) = folder else {
+        return Ok(Vec::new());
+    };
+    let root = PathBuf::from(folder);
+
+    let outcomes = {
+        let conn = db.write().map_err(|e| AppError::SyncError(e.message))?;
+        full_sync(&conn, &root)?
+    };
+    let conflicts = emit_outcomes(app, outcomes);
+
+    let handle = app.clone();
+    let watch_root = root.clone();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| match res {
+        Ok(event) => handle_event(&handle, &watch_root, event),
+        Err(e) => error!("同步文件夹监听错误: {}", e),
+    })
+    .map_err(|e| AppError::SyncError(format!("无法监听同步文件夹: {}", e)))?;
+    watcher
+        .watch(&root, RecursiveMode::Recursive)
+        .map_err(|e| AppError::SyncError(format!("无法监听同步文件夹: {}", e)))?;
+
+    info!("同步文件夹已启动: {:?}", root);
+    if let Some(state) = app.try_state::<SyncHandle>() {
+        if let Ok(mut active) = state.0.lock() {
+            *active = Some(ActiveSync {
+                root,
+                _watcher: watcher,
+            });
+        }
+    }
+
+    Ok(conflicts)
+}
+
+/// 停止监听同步文件夹
+pub fn stop(app: &AppHandle) {
+    if let Some(state) = app.try_state::<SyncHandle>() {
+        if let Ok(mut active) = state.0.lock() {
+            *active = None;
+        }
+    }
+}
+
+/// 当前同步文件夹，未启动同步时返回 None
+pub fn active_root(app: &AppHandle) -> Option<PathBuf> {
+    let state = app.try_state::<SyncHandle>()?;
+    let active = state.0.lock().ok()?;
+    active.as_ref().map(|a| a.root.clone())
+}
+
+/// 数据库变更后在后台写出到同步文件夹；未启用同步时不做任何事
+pub fn spawn_sync(app: AppHandle, scope: SyncScope) {
+    let Some(root) = active_root(&app) else {
+        return;
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let db = app.state::<DbState>();
+        let Ok(conn) = db.write() else {
+            return;
+        };
+
+        let result = match scope {
+            SyncScope::Prompt(id) => export_prompt(&conn, &root, id).map(|o| vec![o]),
+            SyncScope::All => full_sync(&conn, &root),
+        };
+        drop(conn);
+
+        match result {
+            Ok(outcomes) => {
+                emit_outcomes(&app, outcomes);
+            }
+            Err(e) => error!("同步到文件夹失败: {:?}", e),
+        }
+    });
+}
+
+/// 处理文件监听事件
+fn handle_event(app: &AppHandle, root: &Path, event: Event) {
+    if matches!(event.kind, EventKind::Access(_) | EventKind::Other) {
+        return;
+    }
+
+    let db = app.state::<DbState>();
+    let mut outcomes = Vec::new();
+
+    // 改名事件同时带有新旧路径，先处理仍存在的新文件，以便按 uuid 识别为移动
+    let mut paths = event.paths.clone();
+    paths.sort_by_key(|p| !p.exists());
+
+    for path in &paths {
+        let Ok(relative) = path.strip_prefix(root) else {
+            continue;
+        };
+        let hidden = relative.iter().any(|c| c.to_string_lossy().starts_with('.'));
+        if hidden || !markdown_service::is_importable(path) {
+            continue;
+        }
+
+        let relative = relative_path(root, path);
+        let Ok(conn) = db.write() else {
+            return;
+        };
+        let result = if matches!(event.kind, EventKind::Remove(_)) {
+            apply_removal(&conn, root, &relative)
+        } else {
+            apply_file(&conn, root, &relative)
+        };
+
+        match result {
+            Ok(outcome) => outcomes.push(outcome),
+            Err(e) => error!("同步文件 {} 失败: {:?}", relative, e),
+        }
+    }
+
+    emit_outcomes(app, outcomes);
+}
+
+/// 发送冲突和写回事件，返回其中的冲突
+fn emit_outcomes(app: &AppHandle, outcomes: Vec<SyncOutcome>) -> Vec<SyncConflictDto> {
+    let mut conflicts = Vec::new();
+
+    for outcome in outcomes {
+        match outcome {
+            SyncOutcome::Conflict(c) => {
+                if let Err(e) = app.emit(SYNC_CONFLICT_EVENT, c.clone()) {
+                    error!("发送同步冲突事件失败: {}", e);
+                }
+                conflicts.push(c);
+            }
+            SyncOutcome::Applied(prompt_id) => {
+                if let Err(e) = app.emit(SYNC_APPLIED_EVENT, prompt_id) {
+                    error!("发送同步事件失败: {}", e);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    conflicts
+}
+
+/// 记录冲突并返回冲突结果
+fn conflict(
+    conn: &Connection,
+    path: &str,
+    prompt_id: i64,
+    title: Option<String>,
+    content_hash: &str,
+    reason: &str,
+) -> Result<SyncOutcome, AppError> {
+    sync_repository::mark_conflict(conn, prompt_id, path, content_hash, reason)?;
+    info!("同步冲突: prompt_id={}, path={}, {}", prompt_id, path, reason);
+
+    Ok(SyncOutcome::Conflict(SyncConflictDto {
+        prompt_id,
+        title,
+        path: path.to_string(),
+        reason: reason.to_string(),
+        detected_at: Utc::now().to_rfc3339(),
+    }))
+}
+
+/// 文件无法解析：已跟踪的文件标记冲突，未跟踪的文件直接报错
+fn file_error(
+    conn: &Connection,
+    record: Option<SyncRecord>,
+    relative: &str,
+    hash: &str,
+    reason: String,
+) -> Result<SyncOutcome, AppError> {
+    match record {
+        Some(record) => conflict(
+            conn,
+            relative,
+            record.prompt_id,
+            None,
+            hash,
+            &format!("文件无法解析: {}", reason),
+        ),
+        None => Err(AppError::ValidationError(reason)),
+    }
+}
+
+/// 查找 uuid 对应、但原文件已不存在的同步记录（文件在外部被移动或改名）
+///
+/// 改名时若先处理了删除事件，提示词已被移入回收站，这里一并恢复。
+fn find_moved_record(
+    conn: &Connection,
+    root: &Path,
+    uuid: Option<&str>,
+) -> Result<Option<SyncRecord>, AppError> {
+    let Some(uuid) = uuid else {
+        return Ok(None);
+    };
+    let Some(record) = sync_repository::get_record_by_uuid(conn, uuid)? else {
+        return Ok(None);
+    };
+    if record.conflict.is_some() || root.join(&record.path).exists() {
+        return Ok(None);
+    }
+
+    if prompt_repository::get_prompt(conn, record.prompt_id).is_err() {
+        trash_repository::restore_item(conn, TrashKind::Prompt, record.prompt_id)?;
+    }
+
+    Ok(Some(record))
+}
+
+/// 获取未被删除（且上级未被删除）的提示词
+fn load_visible_prompt(conn: &Connection, prompt_id: i64) -> Result<Option<PromptEntryDto>, AppError> {
+    let visible: Option<i64> = conn
+        .query_row(
+            "SELECT pe.id FROM prompt_entries pe
+             JOIN tasks t ON pe.task_id = t.id AND t.deleted_at IS NULL
+             JOIN projects p ON t.project_id = p.id AND p.deleted_at IS NULL
+             WHERE pe.id = ?1 AND pe.deleted_at IS NULL",
+            params![prompt_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    match visible {
+        Some(id) => prompt_repository::get_prompt(conn, id).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// 提示词文件在同步文件夹中的相对路径：`项目/任务/标题-uuid前8位.md`
+fn target_path(conn: &Connection, prompt: &PromptEntryDto) -> Result<String, AppError> {
+    let (project_name, task_name): (String, String) = conn.query_row(
+        "SELECT p.name, t.name FROM tasks t JOIN projects p ON t.project_id = p.id WHERE t.id = ?1",
+        params![prompt.task_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+
+    Ok(format!(
+        "{}/{}/{}-{}.md",
+        sanitize_file_name(&project_name, "未命名项目"),
+        sanitize_file_name(&task_name, "未命名任务"),
+        sanitize_file_name(prompt.title.as_deref().unwrap_or(""), "未命名"),
+        prompt.uuid.chars().take(8).collect::<String>()
+    ))
+}
+
+/// 按文件所在目录查找或创建项目和任务，返回任务 ID
+fn task_for_path(conn: &Connection, relative: &str) -> Result<i64, AppError> {
+    let folders: Vec<&str> = relative.split('/').collect();
+    let folders = &folders[..folders.len().saturating_sub(1)];
+    let project_folder = folders.first().copied().unwrap_or(UNSORTED_NAME);
+    let task_folder = folders.get(1).copied().unwrap_or(UNSORTED_NAME);
+
+    let project_id = match find_by_folder(conn, "SELECT id, name FROM projects WHERE deleted_at IS NULL", [], project_folder)? {
+        Some(id) => id,
+        None => project_repository::create_project(conn, project_folder)?.id,
+    };
+    let task_id = match find_by_folder(
+        conn,
+        "SELECT id, name FROM tasks WHERE project_id = ?1 AND deleted_at IS NULL",
+        [project_id],
+        task_folder,
+    )? {
+        Some(id) => id,
+        None => task_repository::create_task(conn, project_id, task_folder, None)?.id,
+    };
+
+    Ok(task_id)
+}
+
+/// 查找名称转换为目录名后与 `folder` 相同的记录
+fn find_by_folder<P: rusqlite::Params>(
+    conn: &Connection,
+    sql: &str,
+    params: P,
+    folder: &str,
+) -> Result<Option<i64>, AppError> {
+    let rows: Vec<(i64, String)> = conn
+        .prepare(&format!("{} ORDER BY id", sql))?
+        .query_map(params, |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(rows
+        .into_iter()
+        .find(|(_, name)| name == folder || sanitize_file_name(name, "") == folder)
+        .map(|(id, _)| id))
+}
+
+fn db_version(prompt: &PromptEntryDto) -> String {
+    prompt.updated_at.clone().unwrap_or_else(|| prompt.created_at.clone())
+}
+
+/// 计算文件内容的哈希，文件不存在时返回 None
+fn file_hash(path: &Path) -> Result<Option<String>, AppError> {
+    match fs::read(path) {
+        Ok(bytes) => Ok(Some(sha256_hex(&bytes))),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// 先写入同目录下的隐藏临时文件再改名，外部编辑器和文件监听不会读到写了一半的文件
+fn write_file(path: &Path, content: &str) -> Result<(), AppError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let file_name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    let temp_path = path.with_file_name(format!(".{}.tmp", file_name));
+    let result = fs::write(&temp_path, content).and_then(|_| fs::rename(&temp_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+
+    Ok(result?)
+}
+
+/// 相对路径统一使用 `/` 分隔，便于跨平台比较
+fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .iter()
+        .map(|c| c.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::migrations;
+    use crate::models::SortBy;
+    use crate::repositories::tag_repository;
+    use tempfile::TempDir;
+
+    /// 内存数据库 + 临时同步文件夹，并写出一个已同步的提示词
+    fn setup() -> (Connection, TempDir, PromptEntryDto, String) {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("PRAGMA foreign_keys = ON;").unwrap();
+        migrations::run_migrations(&mut conn).unwrap();
+
+        let project = project_repository::create_project(&conn, "P").unwrap();
+        let task = task_repository::create_task(&conn, project.id, "T", None).unwrap();
+        let tags = ["old".to_string()];
+        let prompt = prompt_repository::create_prompt(&conn, task.id, Some("hello"), "body", Some(&tags), None).unwrap();
+
+        let dir = TempDir::new().unwrap();
+        full_sync(&conn, dir.path()).unwrap();
+        let relative = format!("P/T/hello-{}.md", &prompt.uuid[..8]);
+        assert!(dir.path().join(&relative).is_file());
+
+        (conn, dir, prompt, relative)
+    }
+
+    fn read(dir: &TempDir, relative: &str) -> String {
+        fs::read_to_string(dir.path().join(relative)).unwrap()
+    }
+
+    #[test]
+    fn unchanged_prompt_is_not_rewritten() {
+        let (conn, dir, prompt, _) = setup();
+
+        assert!(matches!(export_prompt(&conn, dir.path(), prompt.id).unwrap(), SyncOutcome::Unchanged));
+    }
+
+    #[test]
+    fn external_edit_is_applied() {
+        let (conn, dir, prompt, relative) = setup();
+        fs::write(dir.path().join(&relative), read(&dir, &relative).replace("body", "edited")).unwrap();
+
+        assert!(matches!(apply_file(&conn, dir.path(), &relative).unwrap(), SyncOutcome::Applied(_)));
+        assert_eq!(prompt_repository::get_prompt(&conn, prompt.id).unwrap().content, "edited");
+        // 写回后再次处理同一文件不会重复应用
+        assert!(matches!(apply_file(&conn, dir.path(), &relative).unwrap(), SyncOutcome::Unchanged));
+    }
+
+    #[test]
+    fn both_sides_edited_is_conflict() {
+        let (conn, dir, prompt, relative) = setup();
+        prompt_repository::update_prompt(&conn, prompt.id, None, Some("db side"), None, None).unwrap();
+        fs::write(dir.path().join(&relative), read(&dir, &relative).replace("body", "file side")).unwrap();
+
+        assert!(matches!(export_prompt(&conn, dir.path(), prompt.id).unwrap(), SyncOutcome::Conflict(_)));
+        assert!(read(&dir, &relative).contains("file side"));
+        assert_eq!(sync_repository::list_conflicts(&conn).unwrap().len(), 1);
+
+        resolve_conflict(&conn, dir.path(), prompt.id, SyncResolution::Database).unwrap();
+        assert!(read(&dir, &relative).contains("db side"));
+        assert!(sync_repository::list_conflicts(&conn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn file_deleted_while_db_changed_is_conflict() {
+        let (conn, dir, prompt, relative) = setup();
+        prompt_repository::update_prompt(&conn, prompt.id, None, Some("db side"), None, None).unwrap();
+        fs::remove_file(dir.path().join(&relative)).unwrap();
+
+        assert!(matches!(apply_removal(&conn, dir.path(), &relative).unwrap(), SyncOutcome::Conflict(_)));
+        assert!(prompt_repository::get_prompt(&conn, prompt.id).is_ok());
+
+        resolve_conflict(&conn, dir.path(), prompt.id, SyncResolution::File).unwrap();
+        assert!(prompt_repository::get_prompt(&conn, prompt.id).is_err());
+    }
+
+    #[test]
+    fn file_deleted_without_db_change_moves_prompt_to_trash() {
+        let (conn, dir, prompt, relative) = setup();
+        fs::remove_file(dir.path().join(&relative)).unwrap();
+
+        assert!(matches!(apply_removal(&conn, dir.path(), &relative).unwrap(), SyncOutcome::Applied(_)));
+        assert!(prompt_repository::get_prompt(&conn, prompt.id).is_err());
+    }
+
+    #[test]
+    fn rename_by_uuid_is_move() {
+        let (conn, dir, prompt, relative) = setup();
+        let renamed = "P/T/renamed.md";
+        fs::rename(dir.path().join(&relative), dir.path().join(renamed)).unwrap();
+
+        // 删除事件先到达：提示词暂时进入回收站，随后按 uuid 识别为移动并恢复
+        apply_removal(&conn, dir.path(), &relative).unwrap();
+        assert!(matches!(apply_file(&conn, dir.path(), renamed).unwrap(), SyncOutcome::Unchanged));
+        assert!(prompt_repository::get_prompt(&conn, prompt.id).is_ok());
+        assert_eq!(sync_repository::get_record(&conn, prompt.id).unwrap().unwrap().path, renamed);
+
+        let projects = project_repository::list_projects(&conn, SortBy::CreatedAt).unwrap();
+        let tasks = task_repository::list_tasks_by_project(&conn, projects[0].id, SortBy::CreatedAt).unwrap();
+        assert_eq!((projects.len(), tasks.len()), (1, 1));
+    }
+
+    #[test]
+    fn tag_rename_reexports_file() {
+        let (conn, dir, prompt, relative) = setup();
+        let tag = tag_repository::list_tags(&conn)
+            .unwrap()
+            .into_iter()
+            .find(|t| t.name == "old")
+            .unwrap();
+        tag_repository::rename_tag(&conn, tag.id, "new").unwrap();
+
+        full_sync(&conn, dir.path()).unwrap();
+        let text = read(&dir, &relative);
+        assert!(text.contains("new") && !text.contains("old"));
+
+        // 之后在文件中编辑不会把旧标签写回
+        fs::write(dir.path().join(&relative), text.replace("body", "edited")).unwrap();
+        apply_file(&conn, dir.path(), &relative).unwrap();
+        assert_eq!(
+            prompt_repository::get_prompt(&conn, prompt.id).unwrap().tags,
+            Some(vec!["new".to_string()])
+        );
+    }
+
+    #[test]
+    fn unreadable_tracked_file_does_not_abort_full_sync() {
+        let (conn, dir, prompt, relative) = setup();
+        fs::remove_file(dir.path().join(&relative)).unwrap();
+        fs::create_dir(dir.path().join(&relative)).unwrap();
+        let other = prompt_repository::create_prompt(&conn, prompt.task_id, Some("other"), "x", None, None).unwrap();
+
+        full_sync(&conn, dir.path()).unwrap();
+        assert!(dir.path().join(format!("P/T/other-{}.md", &other.uuid[..8])).is_file());
+    }
+
+    #[test]
+    fn untracked_file_creates_prompt_once() {
+        let (conn, dir, _, _) = setup();
+        fs::create_dir_all(dir.path().join("Q/U")).unwrap();
+        fs::write(dir.path().join("Q/U/new.md"), "---\ntags: [a]\n---\nnew prompt").unwrap();
+
+        let outcomes = full_sync(&conn, dir.path()).unwrap();
+        assert_eq!(outcomes.iter().filter(|o| matches!(o, SyncOutcome::Applied(_))).count(), 1);
+
+        // 第二次同步只会把文件改为规范的文件名，不会重复导入
+        let outcomes = full_sync(&conn, dir.path()).unwrap();
+        assert!(!outcomes.iter().any(|o| matches!(o, SyncOutcome::Applied(_))));
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM prompt_entries WHERE content = 'new prompt'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+}
@@ -0,0 +1,39 @@
+use crate::error::AppError;
+use crate::repositories::{settings_repository, trash_repository};
+use chrono::{Duration, Utc};
+use rusqlite::Connection;
+
+/// 回收站保留天数的设置键
+const RETENTION_DAYS_KEY: &str = "trash_retention_days";
+/// 默认保留天数
+const DEFAULT_RETENTION_DAYS: i64 = 30;
+
+/// 获取回收站保留天数（0 表示不自动清理）
+pub fn get_retention_days(conn: &Connection) -> Result<i64, AppError> {
+    let days = settings_repository::get_setting(conn, RETENTION_DAYS_KEY)?
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or(DEFAULT_RETENTION_DAYS);
+
+    Ok(days)
+}
+
+/// 设置回收站保留天数，并立即按新设置清理过期条目
+pub fn set_retention_days(conn: &Connection, days: i64) -> Result<usize, AppError> {
+    if days < 0 {
+        return Err(AppError::ValidationError("保留天数不能为负数".to_string()));
+    }
+
+    settings_repository::set_setting(conn, RETENTION_DAYS_KEY, &days.to_string())?;
+    purge_expired(conn)
+}
+
+/// 彻底删除超过保留期的回收站条目，返回删除的条目数
+pub fn purge_expired(conn: &Connection) -> Result<usize, AppError> {
+    let days = get_retention_days(conn)?;
+    if days == 0 {
+        return Ok(0);
+    }
+
+    let cutoff = (Utc::now() - Duration::days(days)).to_rfc3339();
+    trash_repository::purge(conn, Some(&cutoff))
+}
@@ -0,0 +1,53 @@
+use std::collections::HashSet;
+
+/// 文件名中保留的最大字符数（不含序号和扩展名）
+const MAX_NAME_CHARS: usize = 60;
+
+/// 将任意文本转换为可在各平台使用的文件名
+///
+/// 去除路径分隔符、Windows 保留字符和控制字符，压缩空白，
+/// 去掉首尾的空格和句点，并截断到固定长度；结果为空时使用 `fallback`。
+pub fn sanitize_file_name(name: &str, fallback: &str) -> String {
+    let mut cleaned = String::new();
+    let mut last_space = false;
+
+    for c in name.chars() {
+        let c = match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c if c.is_control() || c.is_whitespace() => ' ',
+            c => c,
+        };
+        if c == ' ' {
+            if last_space {
+                continue;
+            }
+            last_space = true;
+        } else {
+            last_space = false;
+        }
+        cleaned.push(c);
+    }
+
+    let truncated: String = cleaned.chars().take(MAX_NAME_CHARS).collect();
+    let trimmed = truncated.trim_matches(|c: char| c == ' ' || c == '.');
+
+    if trimmed.is_empty() {
+        fallback.to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// 在同一目录下为重名条目追加序号，如 "名称 (2)"
+///
+/// 比较时忽略大小写，避免在大小写不敏感的文件系统上互相覆盖。
+pub fn unique_name(name: String, used: &mut HashSet<String>) -> String {
+    let mut candidate = name.clone();
+    let mut n = 2;
+    while !used.insert(candidate.to_lowercase()) {
+        candidate = format!("{} ({})", name, n);
+        n += 1;
+    }
+
+    candidate
+}
@@ -0,0 +1,6 @@
+use sha2::{Digest, Sha256};
+
+/// 计算内容的 SHA-256，返回小写十六进制字符串
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
@@ -0,0 +1,6 @@
+use uuid::Uuid;
+
+/// 生成新的全局唯一 ID（UUID v4 字符串）
+pub fn new_uuid() -> String {
+    Uuid::new_v4().to_string()
+}
@@ -0,0 +1,5 @@
+// 通用工具模块
+pub mod file_name;
+pub mod hash;
+pub mod id;
+pub mod pinyin;
@@ -0,0 +1,94 @@
+use pinyin::ToPinyin;
+
+/// 文本的拼音检索键
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PinyinKeys {
+    /// 全拼，如 "用户登录" -> "yonghudenglu"
+    pub full: String,
+    /// 拼音首字母，如 "用户登录" -> "yhdl"
+    pub initials: String,
+}
+
+/// 单个字符对应的拼音片段及其在原文中的字节区间
+struct Segment {
+    full: String,
+    initial: String,
+    start: usize,
+    end: usize,
+}
+
+/// 将文本拆分为拼音片段：汉字转为拼音，字母数字转为小写，其余字符忽略
+fn segments(text: &str) -> Vec<Segment> {
+    text.char_indices()
+        .filter_map(|(start, c)| {
+            let end = start + c.len_utf8();
+            if let Some(py) = c.to_pinyin() {
+                Some(Segment {
+                    full: py.plain().to_string(),
+                    initial: py.first_letter().to_string(),
+                    start,
+                    end,
+                })
+            } else if c.is_alphanumeric() {
+                let lower: String = c.to_lowercase().collect();
+                Some(Segment {
+                    full: lower.clone(),
+                    initial: lower,
+                    start,
+                    end,
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// 计算文本的全拼与首字母检索键
+pub fn pinyin_keys(text: &str) -> PinyinKeys {
+    let mut keys = PinyinKeys::default();
+    for seg in segments(text) {
+        keys.full.push_str(&seg.full);
+        keys.initials.push_str(&seg.initial);
+    }
+    keys
+}
+
+/// 查询词是否可能是拼音（仅由 ASCII 字母数字组成）
+pub fn is_pinyin_query(query: &str) -> bool {
+    !query.is_empty() && query.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// 用拼音查询词匹配文本，返回原文中被命中字符的字节区间
+///
+/// 先尝试首字母匹配（如 "yhdl"），再尝试全拼匹配（如 "yonghu"）。
+pub fn find_pinyin_match(text: &str, query: &str) -> Option<(usize, usize)> {
+    if !is_pinyin_query(query) {
+        return None;
+    }
+
+    let query = query.to_lowercase();
+    let segs = segments(text);
+
+    let initial_match = locate(&segs, &query, |s| &s.initial);
+    initial_match.or_else(|| locate(&segs, &query, |s| &s.full))
+}
+
+/// 在拼接后的拼音串中查找查询词，并映射回原文区间
+fn locate(segs: &[Segment], query: &str, key: impl Fn(&Segment) -> &str) -> Option<(usize, usize)> {
+    let mut joined = String::new();
+    let mut offsets = Vec::with_capacity(segs.len());
+    for seg in segs {
+        offsets.push(joined.len());
+        joined.push_str(key(seg));
+    }
+
+    let pos = joined.find(query)?;
+    let end = pos + query.len();
+
+    // 找出与 [pos, end) 重叠的片段
+    let first = offsets.iter().rposition(|&o| o <= pos)?;
+    let last = offsets.iter().rposition(|&o| o < end)?;
+
+    Some((segs[first].start, segs[last].end))
+}
@@ -153,7 +153,7 @@ export function GlobalSearch({ onSelectResult, isDark = true }: GlobalSearchProp
                                 </span>
                             </div>
                             <p className={`text-sm line-clamp-2 ${styles.resultText}`}>
-                                <HighlightText text={result.snippet} keyword={keyword} isDark={isDark} />
+                                <HighlightText text={result.snippet} keyword={result.highlight ?? keyword} isDark={isDark} />
                             </p>
                             <span className={`text-xs mt-1 block ${styles.panelText}`}>
                                 {new Date(result.created_at).toLocaleString()}
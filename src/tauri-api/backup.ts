@@ -1,15 +1,128 @@
 // Tauri API 封装 - 备份相关
 import { invoke } from "@tauri-apps/api/core";
+import { runOperation } from "./operations";
+import type {
+    BackupFileDto,
+    BackupSettings,
+    ConflictPolicy,
+    CsvColumnMapping,
+    CsvPreview,
+    ExportScope,
+    ImportMode,
+    ImportPreview,
+    ImportReport,
+    ImportTarget,
+} from "../types";
+
+/** 压缩包备份的推荐扩展名 */
+export const ARCHIVE_EXTENSION = ".promptlog.zip";
 
 export const BackupApi = {
     /** 获取数据库路径 */
     getDatabasePath: () => invoke<string>("get_database_path"),
 
-    /** 导出数据 */
-    exportData: (targetPath: string) =>
-        invoke<void>("export_data", { targetPath }),
+    /**
+     * 导出数据（后台执行，完成后返回）
+     *
+     * 提供口令时写入加密文件；否则路径以 .zip 结尾时写入带清单和校验和的压缩包，其余写入 JSON。
+     * 指定 scope 时只导出选中的项目、任务和提示词。
+     */
+    exportData: (targetPath: string, passphrase?: string, scope?: ExportScope) =>
+        runOperation<void>("export_data", { targetPath, passphrase, scope }),
+
+    /**
+     * 导入数据（后台执行，完成后返回导入统计）；默认覆盖模式
+     *
+     * 写入前自动创建数据库快照，可通过 SnapshotApi 恢复。指定 target 时将内容并入该项目或任务（按合并规则，不能与覆盖模式同时使用）。
+     *
+     * 错误码：PASSPHRASE_REQUIRED 文件已加密需要口令，WRONG_PASSPHRASE 口令错误，
+     * BACKUP_CORRUPTED 文件被截断或篡改，VALIDATION_ERROR 格式版本高于当前支持或字段无效（信息中带出错位置，如 $.projects[0].name）。
+     */
+    importData: (
+        sourcePath: string,
+        mode?: ImportMode,
+        conflictPolicy?: ConflictPolicy,
+        passphrase?: string,
+        target?: ImportTarget
+    ) => runOperation<ImportReport>("import_data", { sourcePath, mode, conflictPolicy, passphrase, target }),
+
+    /** 预览导入：返回备份内容统计和与当前数据的差异，不写入数据库；错误码与 importData 相同 */
+    previewImport: (sourcePath: string, passphrase?: string) =>
+        invoke<ImportPreview>("preview_import", { sourcePath, passphrase }),
+
+    /** 获取当前导出格式的 JSON Schema（JSON 字符串） */
+    getExportSchema: () => invoke<string>("get_export_schema"),
+
+    /** 导出为 Markdown 目录（项目/任务/NNN-标题.md），返回写入的文件数 */
+    exportMarkdown: (targetDir: string) =>
+        runOperation<number>("export_markdown", { targetDir }),
+
+    /** 从 Markdown 目录导入（第一级目录为项目，第二级为任务），默认合并到现有数据 */
+    importMarkdown: (sourceDir: string, mode?: ImportMode, conflictPolicy?: ConflictPolicy) =>
+        runOperation<ImportReport>("import_markdown", { sourceDir, mode, conflictPolicy }),
+
+    /**
+     * 导出为静态网站（项目索引、任务页、标签页和本地搜索），返回写入的文件数
+     *
+     * 不引用外部资源，目录可直接打包托管；指定 scope 时只导出选中的项目、任务和提示词。
+     * 目标目录中的 tasks/ 和 tags/ 会先被清空。
+     */
+    exportSite: (targetDir: string, scope?: ExportScope) =>
+        runOperation<number>("export_site", { targetDir, scope }),
+
+    /** 导出为 CSV（每行一条提示词），返回写入的行数 */
+    exportCsv: (targetPath: string) =>
+        runOperation<number>("export_csv", { targetPath }),
+
+    /** 预览 CSV 表头和前几行，并返回推测的列映射 */
+    previewCsv: (sourcePath: string) =>
+        invoke<CsvPreview>("preview_csv", { sourcePath }),
+
+    /** 按列映射导入 CSV，默认合并到现有数据 */
+    importCsv: (
+        sourcePath: string,
+        mapping: CsvColumnMapping,
+        mode?: ImportMode,
+        conflictPolicy?: ConflictPolicy
+    ) => runOperation<ImportReport>("import_csv", { sourcePath, mapping, mode, conflictPolicy }),
+
+    /**
+     * 导入 ChatGPT 数据导出（conversations.json 或官方导出的压缩包），每个对话作为指定项目下的一个任务
+     *
+     * 用户发送的消息作为提示词，短于 minLength 个字符的消息不导入。
+     */
+    importChatgpt: (
+        sourcePath: string,
+        projectId: number,
+        minLength?: number,
+        conflictPolicy?: ConflictPolicy
+    ) => runOperation<ImportReport>("import_chatgpt", { sourcePath, projectId, minLength, conflictPolicy }),
+
+    /**
+     * 导入 Claude.ai 数据导出（conversations.json 或官方导出的压缩包），每个对话作为指定项目下的一个任务
+     *
+     * 重复导入较新的导出时只新增之前没有的消息；短于 minLength 个字符的消息不导入。
+     */
+    importClaude: (
+        sourcePath: string,
+        projectId: number,
+        minLength?: number,
+        conflictPolicy?: ConflictPolicy
+    ) => runOperation<ImportReport>("import_claude", { sourcePath, projectId, minLength, conflictPolicy }),
+
+    /** 获取自动备份设置 */
+    getSettings: () => invoke<BackupSettings>("get_backup_settings"),
+
+    /** 保存自动备份设置 */
+    setSettings: (settings: BackupSettings) => invoke<void>("set_backup_settings", { settings }),
+
+    /** 列出备份目录中的备份（按天 7 份、按周 4 份、按月 12 份轮换），最新的在前 */
+    listBackups: () => invoke<BackupFileDto[]>("list_backups"),
+
+    /** 立即写入一份备份 */
+    createBackup: () => invoke<BackupFileDto>("create_backup"),
 
-    /** 导入数据 */
-    importData: (sourcePath: string) =>
-        invoke<void>("import_data", { sourcePath }),
+    /** 从备份恢复（覆盖现有数据，恢复前会先备份当前数据） */
+    restoreBackup: (fileName: string) =>
+        runOperation<ImportReport>("restore_backup", { fileName }),
 };
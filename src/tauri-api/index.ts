@@ -2,5 +2,11 @@
 export * from "./projects";
 export * from "./tasks";
 export * from "./prompts";
+export * from "./revisions";
+export * from "./tags";
 export * from "./search";
 export * from "./backup";
+export * from "./trash";
+export * from "./operations";
+export * from "./sync";
+export * from "./snapshot";
@@ -0,0 +1,46 @@
+// Tauri API 封装 - 后台长任务
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import type { OperationFinishedEvent } from "../types";
+
+/** 后台任务完成事件名 */
+export const OPERATION_FINISHED_EVENT = "operation-finished";
+
+/**
+ * 启动后台任务并等待其完成事件
+ *
+ * 命令会立即返回任务 ID，真正的结果通过事件送达。
+ * 监听在调用前注册，避免任务过快完成时错过事件。
+ */
+export async function runOperation<T = void>(
+    command: string,
+    args?: Record<string, unknown>
+): Promise<T> {
+    const finished = new Map<string, OperationFinishedEvent<T>>();
+    let waiting: { id: string; resolve: (e: OperationFinishedEvent<T>) => void } | null = null;
+
+    const unlisten = await listen<OperationFinishedEvent<T>>(OPERATION_FINISHED_EVENT, (event) => {
+        const payload = event.payload;
+        if (waiting && waiting.id === payload.operation_id) {
+            waiting.resolve(payload);
+        } else {
+            finished.set(payload.operation_id, payload);
+        }
+    });
+
+    try {
+        const operationId = await invoke<string>(command, args);
+        const payload =
+            finished.get(operationId) ??
+            (await new Promise<OperationFinishedEvent<T>>((resolve) => {
+                waiting = { id: operationId, resolve };
+            }));
+
+        if (!payload.success) {
+            throw payload.error;
+        }
+        return payload.result as T;
+    } finally {
+        unlisten();
+    }
+}
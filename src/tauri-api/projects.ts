@@ -1,10 +1,10 @@
 // Tauri API 封装 - 项目相关
 import { invoke } from "@tauri-apps/api/core";
-import type { ProjectDto } from "../types";
+import type { ProjectDto, SortBy } from "../types";
 
 export const ProjectApi = {
     /** 获取所有项目 */
-    list: () => invoke<ProjectDto[]>("list_projects"),
+    list: (sortBy?: SortBy) => invoke<ProjectDto[]>("list_projects", { sortBy }),
 
     /** 创建项目 */
     create: (name: string) => invoke<ProjectDto>("create_project", { name }),
@@ -14,4 +14,7 @@ export const ProjectApi = {
 
     /** 删除项目 */
     remove: (id: number) => invoke<void>("delete_project", { id }),
+
+    /** 按全局唯一 ID 获取项目 */
+    getByUuid: (uuid: string) => invoke<ProjectDto>("get_project_by_uuid", { uuid }),
 };
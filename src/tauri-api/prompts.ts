@@ -1,12 +1,13 @@
 // Tauri API 封装 - 提示词相关
 import { invoke } from "@tauri-apps/api/core";
-import type { PromptEntryDto } from "../types";
+import type { PromptEntryDto, TagMatchMode } from "../types";
 
 export interface ListPromptsParams {
     taskId: number;
     startTime?: string;
     endTime?: string;
     tags?: string[];
+    tagMode?: TagMatchMode;
 }
 
 export interface CreatePromptParams {
@@ -40,4 +41,7 @@ export const PromptApi = {
 
     /** 删除提示词记录 */
     remove: (id: number) => invoke<void>("delete_prompt_entry", { id }),
+
+    /** 按全局唯一 ID 获取提示词记录 */
+    getByUuid: (uuid: string) => invoke<PromptEntryDto>("get_prompt_entry_by_uuid", { uuid }),
 };
@@ -0,0 +1,25 @@
+// Tauri API 封装 - 修订历史相关
+import { invoke } from "@tauri-apps/api/core";
+import type { PromptEntryDto, PromptRevisionDto } from "../types";
+
+export interface PruneRevisionsParams {
+    promptId?: number;
+    keepLatest?: number;
+    before?: string;
+}
+
+export const RevisionApi = {
+    /** 获取提示词的修订历史 */
+    list: (promptId: number) =>
+        invoke<PromptRevisionDto[]>("list_prompt_revisions", { promptId }),
+
+    /** 获取单条修订 */
+    get: (id: number) => invoke<PromptRevisionDto>("get_prompt_revision", { id }),
+
+    /** 恢复到指定修订 */
+    restore: (id: number) => invoke<PromptEntryDto>("restore_prompt_revision", { id }),
+
+    /** 清理旧修订，返回删除条数 */
+    prune: (params: PruneRevisionsParams) =>
+        invoke<number>("prune_prompt_revisions", { ...params }),
+};
@@ -1,6 +1,6 @@
 // Tauri API 封装 - 搜索相关
 import { invoke } from "@tauri-apps/api/core";
-import type { SearchResultDto } from "../types";
+import type { QuickSwitchItemDto, SearchResultDto } from "../types";
 
 export interface SearchParams {
     keyword: string;
@@ -14,4 +14,8 @@ export const SearchApi = {
     /** 搜索提示词 */
     search: (params: SearchParams) =>
         invoke<SearchResultDto[]>("search_prompt_entries", params),
+
+    /** 快速切换（支持拼音与拼音首字母） */
+    quickSwitch: (keyword: string, limit?: number) =>
+        invoke<QuickSwitchItemDto[]>("quick_switch", { keyword, limit }),
 };
@@ -0,0 +1,15 @@
+// Tauri API 封装 - 数据库快照相关
+import { invoke } from "@tauri-apps/api/core";
+import { runOperation } from "./operations";
+import type { SnapshotDto } from "../types";
+
+export const SnapshotApi = {
+    /** 创建数据库快照 */
+    create: (name: string) => invoke<SnapshotDto>("create_snapshot", { name }),
+
+    /** 列出数据库快照（含导入前自动创建的），最新的在前 */
+    list: () => invoke<SnapshotDto[]>("list_snapshots"),
+
+    /** 从快照恢复（覆盖现有数据），返回恢复前自动创建的快照 */
+    restore: (fileName: string) => runOperation<SnapshotDto>("restore_snapshot", { fileName }),
+};
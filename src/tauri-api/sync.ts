@@ -0,0 +1,24 @@
+// Tauri API 封装 - 同步文件夹相关
+import { invoke } from "@tauri-apps/api/core";
+import type { SyncConflictDto, SyncResolution } from "../types";
+
+/** 检测到同步冲突时发送，载荷为 SyncConflictDto */
+export const SYNC_CONFLICT_EVENT = "sync-conflict";
+
+/** 文件改动写入数据库后发送，载荷为提示词 ID */
+export const SYNC_APPLIED_EVENT = "sync-applied";
+
+export const SyncApi = {
+    /** 获取同步文件夹，未设置时返回 null */
+    getFolder: () => invoke<string | null>("get_sync_folder"),
+
+    /** 设置同步文件夹（传 null 关闭同步），返回首次比对检测到的冲突 */
+    setFolder: (path: string | null) => invoke<SyncConflictDto[]>("set_sync_folder", { path }),
+
+    /** 获取尚未解决的同步冲突 */
+    listConflicts: () => invoke<SyncConflictDto[]>("list_sync_conflicts"),
+
+    /** 解决同步冲突 */
+    resolveConflict: (promptId: number, keep: SyncResolution) =>
+        invoke<void>("resolve_sync_conflict", { promptId, keep }),
+};
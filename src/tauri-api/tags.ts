@@ -0,0 +1,18 @@
+// Tauri API 封装 - 标签相关
+import { invoke } from "@tauri-apps/api/core";
+import type { TagDto } from "../types";
+
+export const TagApi = {
+    /** 获取所有标签及使用次数 */
+    list: () => invoke<TagDto[]>("list_tags"),
+
+    /** 重命名标签 */
+    rename: (id: number, name: string) => invoke<void>("rename_tag", { id, name }),
+
+    /** 将源标签合并到目标标签 */
+    merge: (sourceId: number, targetId: number) =>
+        invoke<void>("merge_tags", { sourceId, targetId }),
+
+    /** 删除标签 */
+    remove: (id: number) => invoke<void>("delete_tag", { id }),
+};
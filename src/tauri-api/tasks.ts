@@ -1,10 +1,11 @@
 // Tauri API 封装 - 任务相关
 import { invoke } from "@tauri-apps/api/core";
-import type { TaskDto } from "../types";
+import type { SortBy, TaskDto } from "../types";
 
 export const TaskApi = {
     /** 获取项目下的任务 */
-    list: (projectId: number) => invoke<TaskDto[]>("list_tasks", { projectId }),
+    list: (projectId: number, sortBy?: SortBy) =>
+        invoke<TaskDto[]>("list_tasks", { projectId, sortBy }),
 
     /** 创建任务 */
     create: (projectId: number, name: string, description?: string | null) =>
@@ -16,4 +17,7 @@ export const TaskApi = {
 
     /** 删除任务 */
     remove: (id: number) => invoke<void>("delete_task", { id }),
+
+    /** 按全局唯一 ID 获取任务 */
+    getByUuid: (uuid: string) => invoke<TaskDto>("get_task_by_uuid", { uuid }),
 };
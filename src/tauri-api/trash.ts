@@ -0,0 +1,20 @@
+// Tauri API 封装 - 回收站相关
+import { invoke } from "@tauri-apps/api/core";
+import type { TrashItemDto, TrashKind } from "../types";
+
+export const TrashApi = {
+    /** 获取回收站条目 */
+    list: () => invoke<TrashItemDto[]>("list_trash"),
+
+    /** 恢复条目 */
+    restore: (kind: TrashKind, id: number) => invoke<void>("restore_item", { kind, id }),
+
+    /** 彻底删除单个条目；不传参数时清空回收站。返回删除条数 */
+    purge: (kind?: TrashKind, id?: number) => invoke<number>("purge_trash", { kind, id }),
+
+    /** 获取回收站保留天数（0 表示不自动清理） */
+    getRetentionDays: () => invoke<number>("get_trash_retention_days"),
+
+    /** 设置回收站保留天数，返回立即清理的条目数 */
+    setRetentionDays: (days: number) => invoke<number>("set_trash_retention_days", { days }),
+};
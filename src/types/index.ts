@@ -3,6 +3,8 @@
 /** 项目 */
 export interface ProjectDto {
     id: number;
+    /** 全局唯一 ID，导入导出时保持不变 */
+    uuid: string;
     name: string;
     created_at: string;
     updated_at?: string | null;
@@ -11,6 +13,8 @@ export interface ProjectDto {
 /** 任务 */
 export interface TaskDto {
     id: number;
+    /** 全局唯一 ID，导入导出时保持不变 */
+    uuid: string;
     project_id: number;
     name: string;
     description?: string | null;
@@ -21,6 +25,8 @@ export interface TaskDto {
 /** 提示词记录 */
 export interface PromptEntryDto {
     id: number;
+    /** 全局唯一 ID，导入导出时保持不变 */
+    uuid: string;
     task_id: number;
     title?: string | null;
     content: string;
@@ -30,6 +36,31 @@ export interface PromptEntryDto {
     updated_at?: string | null;
 }
 
+/** 标签 */
+export interface TagDto {
+    id: number;
+    name: string;
+    /** 使用该标签的提示词数量 */
+    usage_count: number;
+}
+
+/** 多标签筛选方式：any 命中任一，all 须全部包含 */
+export type TagMatchMode = "any" | "all";
+
+/** 提示词修订记录 */
+export interface PromptRevisionDto {
+    id: number;
+    prompt_id: number;
+    title?: string | null;
+    content: string;
+    tags?: string[] | null;
+    model?: string | null;
+    /** 该版本内容最后一次编辑的时间 */
+    version_at: string;
+    /** 该版本被替换的时间 */
+    created_at: string;
+}
+
 /** 搜索结果 */
 export interface SearchResultDto {
     project_id: number;
@@ -38,9 +69,222 @@ export interface SearchResultDto {
     project_name: string;
     task_name: string;
     snippet: string;
+    /** 摘要中实际命中的原文片段，用于高亮 */
+    highlight?: string | null;
     created_at: string;
 }
 
+/** 快速切换结果 */
+export interface QuickSwitchItemDto {
+    kind: "project" | "task" | "prompt";
+    id: number;
+    project_id: number;
+    task_id?: number | null;
+    name: string;
+    context?: string | null;
+    highlight?: string | null;
+}
+
+/** 名称列表排序方式 */
+export type SortBy = "created_at" | "pinyin";
+
+/** 回收站条目类型 */
+export type TrashKind = "project" | "task" | "prompt";
+
+/** 回收站条目 */
+export interface TrashItemDto {
+    kind: TrashKind;
+    id: number;
+    name: string;
+    context?: string | null;
+    deleted_at: string;
+}
+
+/** 导入模式：覆盖或合并 */
+export type ImportMode = "replace" | "merge";
+
+/** 合并导入时遇到内容相同的提示词的处理策略 */
+export type ConflictPolicy = "skip" | "overwrite" | "keep_both";
+
+/** 导入目标：将备份内容并入已有的项目（任务并入）或任务（提示词并入） */
+export interface ImportTarget {
+    kind: "project" | "task";
+    id: number;
+}
+
+/** 导入结果统计 */
+export interface ImportReport {
+    projects_added: number;
+    projects_merged: number;
+    tasks_added: number;
+    tasks_merged: number;
+    prompts_added: number;
+    prompts_updated: number;
+    prompts_skipped: number;
+    /** 无法解析而被跳过的文件 */
+    failed_files: ImportFailure[];
+}
+
+/** 导入时无法解析的文件 */
+export interface ImportFailure {
+    path: string;
+    reason: string;
+}
+
+/** 导入预览：备份内容统计及与当前数据的差异 */
+export interface ImportPreview {
+    /** 备份文件的导出格式版本 */
+    version: number;
+    exported_at: string;
+    project_count: number;
+    task_count: number;
+    prompt_count: number;
+    /** 提示词创建时间的范围 */
+    earliest_at?: string | null;
+    latest_at?: string | null;
+    projects: ProjectPreview[];
+    diff: ImportDiff;
+}
+
+/** 备份中单个项目的统计 */
+export interface ProjectPreview {
+    name: string;
+    prompt_count: number;
+    tasks: TaskPreview[];
+}
+
+/** 备份中单个任务的统计 */
+export interface TaskPreview {
+    name: string;
+    prompt_count: number;
+}
+
+/** 备份与当前数据的差异；removed 只在覆盖导入时才会真正删除 */
+export interface ImportDiff {
+    projects: DiffCounts;
+    tasks: DiffCounts;
+    prompts: DiffCounts;
+    /** 有差异的提示词明细（数量有上限） */
+    items: DiffItem[];
+}
+
+export interface DiffCounts {
+    added: number;
+    removed: number;
+    changed: number;
+    unchanged: number;
+}
+
+export type DiffChange = "added" | "removed" | "changed";
+
+/** 单条提示词差异 */
+export interface DiffItem {
+    change: DiffChange;
+    project: string;
+    task: string;
+    title?: string | null;
+    uuid?: string | null;
+}
+
+/** 自动备份设置 */
+export interface BackupSettings {
+    enabled: boolean;
+    /** 两次自动备份之间的间隔（小时） */
+    interval_hours: number;
+    /** 备份目录，为空时使用应用数据目录下的 backups/ */
+    folder?: string | null;
+}
+
+/** 备份文件信息 */
+export interface BackupFileDto {
+    file_name: string;
+    path: string;
+    created_at: string;
+    size: number;
+}
+
+/** 导出范围：选中的项目、任务和提示词的并集；单独选中的提示词连同所属任务和项目一起导出 */
+export interface ExportScope {
+    project_ids?: number[];
+    task_ids?: number[];
+    prompt_ids?: number[];
+}
+
+/** 数据库快照信息 */
+export interface SnapshotDto {
+    file_name: string;
+    /** 创建时指定的名称 */
+    name: string;
+    created_at: string;
+    size: number;
+    /** 导入、恢复前自动创建的快照 */
+    automatic: boolean;
+}
+
+/** CSV 列映射：各字段对应的列序号（从 0 开始），只有内容列必填 */
+export interface CsvColumnMapping {
+    project?: number | null;
+    task?: number | null;
+    title?: number | null;
+    content: number | null;
+    tags?: number | null;
+    model?: number | null;
+    created_at?: number | null;
+    updated_at?: number | null;
+    uuid?: number | null;
+    /** 没有项目列或该列为空时使用的项目名 */
+    default_project?: string | null;
+    /** 没有任务列或该列为空时使用的任务名 */
+    default_task?: string | null;
+}
+
+/** CSV 预览 */
+export interface CsvPreview {
+    headers: string[];
+    /** 前几行数据 */
+    rows: string[][];
+    /** 总数据行数（不含表头） */
+    total_rows: number;
+    /** 按表头推测的列映射 */
+    suggested_mapping: CsvColumnMapping;
+}
+
+/** 后台长任务类型 */
+export type OperationKind =
+    | "export"
+    | "import"
+    | "markdown_export"
+    | "markdown_import"
+    | "site_export"
+    | "csv_export"
+    | "csv_import"
+    | "chatgpt_import"
+    | "claude_import"
+    | "backup_restore"
+    | "snapshot_restore";
+
+/** 后台长任务完成事件 */
+export interface OperationFinishedEvent<T = unknown> {
+    operation_id: string;
+    kind: OperationKind;
+    success: boolean;
+    result?: T | null;
+    error?: ApiError | null;
+}
+
+/** 同步冲突：数据库和文件都发生了修改 */
+export interface SyncConflictDto {
+    prompt_id: number;
+    title?: string | null;
+    /** 相对于同步文件夹的路径 */
+    path: string;
+    reason: string;
+    detected_at: string;
+}
+
+/** 解决同步冲突时保留的一方 */
+export type SyncResolution = "database" | "file";
+
 /** API 错误 */
 export interface ApiError {
     code: string;